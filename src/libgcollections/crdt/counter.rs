@@ -0,0 +1,193 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crdt::ReplicaId;
+use ops::*;
+use ops::lattice::product::product_entailment;
+use trilean::SKleene;
+use std::collections::BTreeMap;
+
+/// Grow-only counter: every replica counts its own increments, the value is the sum of the counts.
+/// It is also a version vector, where the count of a replica is the number of events it has produced.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct GCounter
+{
+  /// The replicas with a count of zero are not stored.
+  counts: BTreeMap<ReplicaId, u64>
+}
+
+impl GCounter
+{
+  pub fn new() -> GCounter {
+    GCounter {
+      counts: BTreeMap::new()
+    }
+  }
+
+  pub fn increment(&mut self, replica: ReplicaId) {
+    self.increment_by(replica, 1);
+  }
+
+  pub fn increment_by(&mut self, replica: ReplicaId, n: u64) {
+    if n > 0 {
+      *self.counts.entry(replica).or_insert(0) += n;
+    }
+  }
+
+  /// Count of the increments of `replica`.
+  pub fn count(&self, replica: ReplicaId) -> u64 {
+    self.counts.get(&replica).cloned().unwrap_or(0)
+  }
+
+  /// The sum is computed over `u128` so it cannot overflow.
+  pub fn value(&self) -> u128 {
+    self.counts.values().map(|n| *n as u128).sum()
+  }
+}
+
+fn pointwise_entailment(a: &GCounter, b: &GCounter) -> SKleene {
+  SKleene::from_bool(b.counts.iter().all(|(replica, n)| a.count(*replica) >= *n))
+}
+
+impl Join for GCounter
+{
+  fn join(mut self, other: GCounter) -> GCounter {
+    for (replica, n) in other.counts {
+      let count = self.counts.entry(replica).or_insert(0);
+      *count = (*count).max(n);
+    }
+    self
+  }
+}
+
+impl Entailment for GCounter
+{
+  fn entail(&self, other: &GCounter) -> SKleene {
+    product_entailment(pointwise_entailment(self, other),
+      || pointwise_entailment(other, self))
+  }
+}
+
+impl Bot for GCounter
+{
+  fn bot() -> GCounter {
+    GCounter::new()
+  }
+}
+
+/// Counter with increments and decrements, represented by two grow-only counters.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct PNCounter
+{
+  increments: GCounter,
+  decrements: GCounter
+}
+
+impl PNCounter
+{
+  pub fn new() -> PNCounter {
+    PNCounter {
+      increments: GCounter::new(),
+      decrements: GCounter::new()
+    }
+  }
+
+  pub fn increment(&mut self, replica: ReplicaId) {
+    self.increments.increment(replica);
+  }
+
+  pub fn decrement(&mut self, replica: ReplicaId) {
+    self.decrements.increment(replica);
+  }
+
+  /// The value is exact since the counts of `GCounter::value` fit in `i128`.
+  pub fn value(&self) -> i128 {
+    self.increments.value() as i128 - self.decrements.value() as i128
+  }
+}
+
+impl Join for PNCounter
+{
+  fn join(self, other: PNCounter) -> PNCounter {
+    PNCounter {
+      increments: self.increments.join(other.increments),
+      decrements: self.decrements.join(other.decrements)
+    }
+  }
+}
+
+impl Entailment for PNCounter
+{
+  fn entail(&self, other: &PNCounter) -> SKleene {
+    let ab = self.increments.entail(&other.increments).and(self.decrements.entail(&other.decrements));
+    product_entailment(ab,
+      || other.increments.entail(&self.increments).and(other.decrements.entail(&self.decrements)))
+  }
+}
+
+impl Bot for PNCounter
+{
+  fn bot() -> PNCounter {
+    PNCounter::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crdt::test::*;
+  use trilean::SKleene::*;
+
+  #[test]
+  fn g_counter_test() {
+    let mut a = GCounter::new();
+    a.increment(0);
+    a.increment_by(1, 3);
+    a.increment_by(2, 0);
+    assert_eq!(a.value(), 4);
+    assert_eq!(a.count(2), 0);
+    let mut b = GCounter::new();
+    b.increment_by(1, 5);
+    assert_eq!(a.entail(&b), Unknown);
+    let c = a.clone().join(b.clone());
+    assert_eq!(c.value(), 6);
+    assert_eq!(c.entail(&a), True);
+    assert_eq!(b.entail(&c), False);
+    let mut big = GCounter::new();
+    big.increment_by(0, u64::MAX);
+    big.increment_by(1, u64::MAX);
+    assert_eq!(big.value(), 2 * u64::MAX as u128);
+
+    ConvergenceTester::new(0, 4, |rng: &mut Rng, replica, counter: &mut GCounter|
+        counter.increment_by(replica, rng.below(3)))
+      .seed(test_seed(0))
+      .test_all();
+  }
+
+  #[test]
+  fn pn_counter_test() {
+    let mut a = PNCounter::new();
+    a.increment(0);
+    a.decrement(0);
+    a.decrement(0);
+    assert_eq!(a.value(), -1);
+    let mut b = PNCounter::new();
+    b.increment(1);
+    assert_eq!(a.clone().join(b).value(), 0);
+    let mut c = PNCounter::new();
+    c.decrements.increment_by(0, u64::MAX);
+    c.decrements.increment_by(1, u64::MAX);
+    c.increment(2);
+    assert_eq!(c.value(), 1 - 2 * u64::MAX as i128);
+
+    ConvergenceTester::new(1, 4, |rng: &mut Rng, replica, counter: &mut PNCounter|
+        if rng.next_bool() { counter.increment(replica) } else { counter.decrement(replica) })
+      .seed(test_seed(1))
+      .test_all();
+  }
+}
@@ -0,0 +1,46 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! State-based [CRDTs](https://en.wikipedia.org/wiki/Conflict-free_replicated_data_type) (convergent replicated data types).
+//!
+//! The state of a replica is an element of a join-semilattice: every update moves the state upwards, and two replicas are merged with `Join`.
+//! Since the join is associative, commutative and idempotent, replicas that have received the same updates converge to the same state whatever the order of the merges.
+//! Every type implements `Join`, `Entailment` and `Bot` (the initial state), hence `BoundedJoinSemilattice`.
+//!
+//! * Counters: `GCounter` (grow-only) and `PNCounter` (increment and decrement).
+//! * Sets: `GSet` (grow-only), `TwoPSet` (an element cannot be added again once removed) and `ORSet` (observed-remove, an element can be added again).
+//! * Registers: `LWWRegister` (last writer wins) and `MVRegister` (multi-value, keeps the concurrent writes).
+//!
+//! The operations taking a `ReplicaId` must be called with the identifier of the replica owning the state.
+//! The module `test` simulates several replicas to check the convergence of a CRDT.
+//!
+//! ```rust
+//! use gcollections::ops::*;
+//! use gcollections::crdt::*;
+//!
+//! let mut a = GCounter::new();
+//! let mut b = GCounter::new();
+//! a.increment(0);
+//! b.increment(1);
+//! b.increment(1);
+//! let merged = a.clone().join(b.clone());
+//! assert_eq!(merged, b.join(a));
+//! assert_eq!(merged.value(), 3);
+//! ```
+
+pub mod counter;
+pub mod set;
+pub mod register;
+pub mod test;
+
+pub use self::counter::*;
+pub use self::set::*;
+pub use self::register::*;
+
+/// Identifier of a replica.
+pub type ReplicaId = usize;
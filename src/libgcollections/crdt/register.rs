@@ -0,0 +1,219 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crdt::ReplicaId;
+use crdt::counter::GCounter;
+use ops::*;
+use ops::lattice::product::product_entailment;
+use trilean::SKleene;
+use trilean::SKleene::*;
+
+/// Last-writer-wins register: every write is stamped with a timestamp, and the merge keeps the write with the greatest timestamp.
+/// Concurrent writes with the same timestamp are ordered by the replica identifier and then by the value, so the register is a chain.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LWWRegister<T>
+{
+  write: Option<(u64, ReplicaId, T)>
+}
+
+impl<T: Ord> LWWRegister<T>
+{
+  pub fn new() -> LWWRegister<T> {
+    LWWRegister {
+      write: None
+    }
+  }
+
+  /// The write is ignored if it is older than the current value.
+  pub fn assign(&mut self, replica: ReplicaId, timestamp: u64, value: T) {
+    let write = Some((timestamp, replica, value));
+    if write > self.write {
+      self.write = write;
+    }
+  }
+
+  pub fn value(&self) -> Option<&T> {
+    self.write.as_ref().map(|(_, _, value)| value)
+  }
+
+  pub fn timestamp(&self) -> Option<u64> {
+    self.write.as_ref().map(|(timestamp, _, _)| *timestamp)
+  }
+}
+
+impl<T: Ord> Default for LWWRegister<T>
+{
+  fn default() -> LWWRegister<T> {
+    LWWRegister::new()
+  }
+}
+
+impl<T: Ord> Join for LWWRegister<T>
+{
+  fn join(self, other: LWWRegister<T>) -> LWWRegister<T> {
+    if self.write >= other.write { self } else { other }
+  }
+}
+
+impl<T: Ord> Entailment for LWWRegister<T>
+{
+  fn entail(&self, other: &LWWRegister<T>) -> SKleene {
+    SKleene::from_bool(self.write >= other.write)
+  }
+}
+
+impl<T: Ord> Bot for LWWRegister<T>
+{
+  fn bot() -> LWWRegister<T> {
+    LWWRegister::new()
+  }
+}
+
+/// Multi-value register: every write is stamped with a version vector, and the merge keeps all the writes that are not overwritten by another one.
+/// Reading the register gives the values of the concurrent writes.
+#[derive(Clone, Debug)]
+pub struct MVRegister<T>
+{
+  /// The writes are pairwise distinct and their version vectors are pairwise incomparable.
+  /// They are stored in a vector since version vectors are only partially ordered.
+  writes: Vec<(GCounter, T)>
+}
+
+impl<T: Ord> MVRegister<T>
+{
+  pub fn new() -> MVRegister<T> {
+    MVRegister {
+      writes: vec![]
+    }
+  }
+
+  /// Overwrites all the values currently in the register.
+  pub fn assign(&mut self, replica: ReplicaId, value: T) {
+    let mut version = GCounter::new();
+    for (v, _) in &self.writes {
+      version = version.join(v.clone());
+    }
+    version.increment(replica);
+    self.writes.clear();
+    self.writes.push((version, value));
+  }
+
+  pub fn values(&self) -> Vec<&T> {
+    self.writes.iter().map(|(_, value)| value).collect()
+  }
+}
+
+/// The writes are compared as sets, regardless of the order in which they are stored.
+impl<T: Ord> PartialEq for MVRegister<T>
+{
+  fn eq(&self, other: &MVRegister<T>) -> bool {
+    self.writes.len() == other.writes.len() &&
+    self.writes.iter().all(|write| other.writes.contains(write))
+  }
+}
+
+impl<T: Ord> Eq for MVRegister<T> {}
+
+impl<T: Ord> Default for MVRegister<T>
+{
+  fn default() -> MVRegister<T> {
+    MVRegister::new()
+  }
+}
+
+/// `True` if every write of `b` is overwritten by, or equal to, a write of `a`.
+fn covers<T: Ord>(a: &MVRegister<T>, b: &MVRegister<T>) -> SKleene {
+  SKleene::from_bool(b.writes.iter().all(|(vb, x)|
+    a.writes.iter().any(|(va, y)| match va.entail(vb) {
+      True => va != vb || x == y,
+      _ => false
+    })))
+}
+
+impl<T: Ord + Clone> Join for MVRegister<T>
+{
+  fn join(self, other: MVRegister<T>) -> MVRegister<T> {
+    let mut writes = self.writes;
+    for write in other.writes {
+      if !writes.contains(&write) {
+        writes.push(write);
+      }
+    }
+    let writes = writes.iter()
+      .filter(|(v, _)| !writes.iter().any(|(w, _)| w.strict_entail(v) == True))
+      .cloned()
+      .collect();
+    MVRegister {
+      writes: writes
+    }
+  }
+}
+
+impl<T: Ord> Entailment for MVRegister<T>
+{
+  fn entail(&self, other: &MVRegister<T>) -> SKleene {
+    product_entailment(covers(self, other), || covers(other, self))
+  }
+}
+
+impl<T: Ord> Bot for MVRegister<T>
+{
+  fn bot() -> MVRegister<T> {
+    MVRegister::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crdt::test::*;
+
+  #[test]
+  fn lww_register_test() {
+    let mut a = LWWRegister::new();
+    a.assign(0, 2, "a");
+    a.assign(0, 1, "old");
+    assert_eq!(a.value(), Some(&"a"));
+    let mut b = LWWRegister::new();
+    b.assign(1, 2, "b");
+    assert_eq!(b.entail(&a), True);
+    assert_eq!(a.clone().join(b.clone()).value(), Some(&"b"));
+    assert_eq!(b.clone().join(a).timestamp(), Some(2));
+
+    ConvergenceTester::new(0, 3, |rng: &mut Rng, replica, register: &mut LWWRegister<u64>| {
+        let timestamp = register.timestamp().map_or(0, |t| t + rng.below(2));
+        register.assign(replica, timestamp, rng.below(4))
+      })
+      .seed(test_seed(0))
+      .test_all();
+  }
+
+  #[test]
+  fn mv_register_test() {
+    let mut a = MVRegister::new();
+    a.assign(0, 1);
+    let mut b = a.clone();
+    a.assign(0, 2);
+    b.assign(1, 3);
+    let c = a.clone().join(b.clone());
+    let mut values = c.values();
+    values.sort();
+    assert_eq!(values, vec![&2, &3]);
+    assert_eq!(a.entail(&b), Unknown);
+    assert_eq!(c.entail(&a), True);
+    let mut d = c.clone();
+    d.assign(1, 4);
+    assert_eq!(d.values(), vec![&4]);
+    assert_eq!(d.clone().join(c).values(), vec![&4]);
+
+    ConvergenceTester::new(1, 3, |rng: &mut Rng, replica, register: &mut MVRegister<u64>|
+        register.assign(replica, rng.below(4)))
+      .seed(test_seed(1))
+      .test_all();
+  }
+}
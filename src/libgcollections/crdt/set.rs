@@ -0,0 +1,294 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crdt::ReplicaId;
+use ops::*;
+use ops::lattice::product::product_entailment;
+use wrappers::powerset::subset_entailment;
+use wrappers::btree_set::BTreeSet;
+use trilean::SKleene;
+
+/// Grow-only set, the merge is the union.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GSet<T>
+{
+  elements: BTreeSet<T>
+}
+
+impl<T: Ord> GSet<T>
+{
+  pub fn new() -> GSet<T> {
+    GSet {
+      elements: BTreeSet::empty()
+    }
+  }
+
+  pub fn insert(&mut self, value: T) {
+    Insert::insert(&mut self.elements, value);
+  }
+
+  pub fn contains(&self, value: &T) -> bool {
+    Contains::contains(&self.elements, value)
+  }
+
+  pub fn elements(&self) -> &BTreeSet<T> {
+    &self.elements
+  }
+}
+
+impl<T: Ord> Default for GSet<T>
+{
+  fn default() -> GSet<T> {
+    GSet::new()
+  }
+}
+
+impl<T: Ord> Join for GSet<T>
+{
+  fn join(self, other: GSet<T>) -> GSet<T> {
+    GSet {
+      elements: self.elements.join(other.elements)
+    }
+  }
+}
+
+impl<T: Ord> Entailment for GSet<T>
+{
+  fn entail(&self, other: &GSet<T>) -> SKleene {
+    self.elements.entail(&other.elements)
+  }
+}
+
+impl<T: Ord> Bot for GSet<T>
+{
+  fn bot() -> GSet<T> {
+    GSet::new()
+  }
+}
+
+/// Two-phase set: the removed elements are kept in a second grow-only set (the tombstones), thus an element cannot be added again once it has been removed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TwoPSet<T>
+{
+  added: GSet<T>,
+  removed: GSet<T>
+}
+
+impl<T: Ord + Clone> TwoPSet<T>
+{
+  pub fn new() -> TwoPSet<T> {
+    TwoPSet {
+      added: GSet::new(),
+      removed: GSet::new()
+    }
+  }
+
+  pub fn insert(&mut self, value: T) {
+    self.added.insert(value);
+  }
+
+  /// Only an element of the set can be removed, otherwise nothing happens.
+  pub fn remove(&mut self, value: &T) {
+    if self.contains(value) {
+      self.removed.insert(value.clone());
+    }
+  }
+
+  pub fn contains(&self, value: &T) -> bool {
+    self.added.contains(value) && !self.removed.contains(value)
+  }
+
+  pub fn elements(&self) -> Vec<&T> {
+    self.added.elements().iter().filter(|x| !self.removed.contains(x)).collect()
+  }
+}
+
+impl<T: Ord + Clone> Default for TwoPSet<T>
+{
+  fn default() -> TwoPSet<T> {
+    TwoPSet::new()
+  }
+}
+
+impl<T: Ord> Join for TwoPSet<T>
+{
+  fn join(self, other: TwoPSet<T>) -> TwoPSet<T> {
+    TwoPSet {
+      added: self.added.join(other.added),
+      removed: self.removed.join(other.removed)
+    }
+  }
+}
+
+impl<T: Ord> Entailment for TwoPSet<T>
+{
+  fn entail(&self, other: &TwoPSet<T>) -> SKleene {
+    let ab = self.added.entail(&other.added).and(self.removed.entail(&other.removed));
+    product_entailment(ab, || other.added.entail(&self.added).and(other.removed.entail(&self.removed)))
+  }
+}
+
+impl<T: Ord + Clone> Bot for TwoPSet<T>
+{
+  fn bot() -> TwoPSet<T> {
+    TwoPSet::new()
+  }
+}
+
+/// Unique identifier of an insertion: the replica performing it and a sequence number local to this replica.
+pub type Tag = (ReplicaId, u64);
+
+/// Observed-remove set: every insertion is tagged, and a removal only removes the tags observed by the replica.
+/// An element is in the set if one of its tags is not removed, hence an insertion concurrent to a removal wins, and an element can be added again after its removal.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ORSet<T>
+{
+  added: BTreeSet<(T, Tag)>,
+  removed: BTreeSet<(T, Tag)>
+}
+
+impl<T: Ord + Clone> ORSet<T>
+{
+  pub fn new() -> ORSet<T> {
+    ORSet {
+      added: BTreeSet::empty(),
+      removed: BTreeSet::empty()
+    }
+  }
+
+  /// The sequence number of the tag is computed from the previous insertions of `replica`, which takes a time linear in the number of insertions.
+  pub fn insert(&mut self, replica: ReplicaId, value: T) {
+    let sequence = self.added.iter()
+      .filter(|(_, tag)| tag.0 == replica)
+      .map(|(_, tag)| tag.1 + 1)
+      .max()
+      .unwrap_or(0);
+    Insert::insert(&mut self.added, (value, (replica, sequence)));
+  }
+
+  /// Removes the observed insertions of `value`.
+  pub fn remove(&mut self, value: &T) {
+    let observed: Vec<_> = self.live_tags(value).cloned().collect();
+    self.removed.extend(observed);
+  }
+
+  pub fn contains(&self, value: &T) -> bool {
+    self.live_tags(value).next().is_some()
+  }
+
+  pub fn elements(&self) -> Vec<&T> {
+    let mut elements: Vec<&T> = self.added.iter()
+      .filter(|entry| !Contains::contains(&self.removed, *entry))
+      .map(|(value, _)| value)
+      .collect();
+    elements.dedup();
+    elements
+  }
+
+  fn live_tags<'a>(&'a self, value: &'a T) -> impl Iterator<Item=&'a (T, Tag)> + 'a {
+    self.added.iter()
+      .filter(move |entry| &entry.0 == value && !Contains::contains(&self.removed, *entry))
+  }
+}
+
+impl<T: Ord + Clone> Default for ORSet<T>
+{
+  fn default() -> ORSet<T> {
+    ORSet::new()
+  }
+}
+
+impl<T: Ord> Join for ORSet<T>
+{
+  fn join(self, other: ORSet<T>) -> ORSet<T> {
+    ORSet {
+      added: self.added.join(other.added),
+      removed: self.removed.join(other.removed)
+    }
+  }
+}
+
+impl<T: Ord> Entailment for ORSet<T>
+{
+  fn entail(&self, other: &ORSet<T>) -> SKleene {
+    let ab = subset_entailment(&self.added, &other.added).and(subset_entailment(&self.removed, &other.removed));
+    product_entailment(ab,
+      || subset_entailment(&other.added, &self.added).and(subset_entailment(&other.removed, &self.removed)))
+  }
+}
+
+impl<T: Ord + Clone> Bot for ORSet<T>
+{
+  fn bot() -> ORSet<T> {
+    ORSet::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crdt::test::*;
+  use trilean::SKleene::*;
+
+  #[test]
+  fn g_set_test() {
+    let mut a = GSet::new();
+    a.insert(1);
+    let mut b = GSet::new();
+    b.insert(2);
+    assert_eq!(a.entail(&b), Unknown);
+    let c = a.clone().join(b);
+    assert!(c.contains(&1) && c.contains(&2));
+    assert_eq!(c.entail(&a), True);
+
+    ConvergenceTester::new(0, 3, |rng: &mut Rng, _, set: &mut GSet<u64>| set.insert(rng.below(8)))
+      .seed(test_seed(0))
+      .test_all();
+  }
+
+  #[test]
+  fn two_p_set_test() {
+    let mut a = TwoPSet::new();
+    a.insert(1);
+    a.remove(&1);
+    a.insert(1);
+    assert!(!a.contains(&1));
+    let mut b = TwoPSet::new();
+    b.insert(1);
+    b.insert(2);
+    let c = a.join(b);
+    assert_eq!(c.elements(), vec![&2]);
+
+    ConvergenceTester::new(1, 3, |rng: &mut Rng, _, set: &mut TwoPSet<u64>|
+        if rng.next_bool() { set.insert(rng.below(8)) } else { set.remove(&rng.below(8)) })
+      .seed(test_seed(1))
+      .test_all();
+  }
+
+  #[test]
+  fn or_set_test() {
+    let mut a = ORSet::new();
+    a.insert(0, 1);
+    let mut b = a.clone();
+    // Concurrent removal and insertion of `1`: the insertion wins.
+    a.remove(&1);
+    b.insert(1, 1);
+    assert!(!a.contains(&1));
+    let c = a.clone().join(b);
+    assert!(c.contains(&1));
+    // An element can be added again after being removed.
+    a.insert(0, 1);
+    assert!(a.contains(&1));
+    assert_eq!(a.elements(), vec![&1]);
+
+    ConvergenceTester::new(2, 3, |rng: &mut Rng, replica, set: &mut ORSet<u64>|
+        if rng.next_bool() { set.insert(replica, rng.below(4)) } else { set.remove(&rng.below(4)) })
+      .seed(test_seed(2))
+      .test_all();
+  }
+}
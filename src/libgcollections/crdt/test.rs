@@ -0,0 +1,149 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Simulation of replicas performing random updates and merging their states in random orders.
+
+use crdt::ReplicaId;
+use ops::*;
+use ops::lattice::test::JoinSemilatticeTester;
+use trilean::SKleene::True;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+pub use ops::lattice::test::{Rng, Arbitrary, clock_seed, test_seed};
+
+/// Simulates `replicas` replicas starting from `bot`.
+/// At each step, a random replica either performs a random update with `operation`, or merges the state of another random replica.
+/// The updates must be inflationary: the new state must entail the previous one.
+///
+/// At the end of the simulation, the states are merged in random orders (with duplicates) and every order must give the same state.
+/// The replicas then gossip their states pairwise in random orders until quiescence, and they must all converge to this state.
+/// The laws of join-semilattices are also checked on the states reached during the simulation.
+pub struct ConvergenceTester<S, O>
+{
+  test_id: usize,
+  replicas: usize,
+  operation: O,
+  seed: u64,
+  steps: usize,
+  merge_orders: usize,
+  phantom_state: PhantomData<S>
+}
+
+impl<S, O> ConvergenceTester<S, O> where
+  S: BoundedJoinSemilattice + Clone + Debug + Eq,
+  O: FnMut(&mut Rng, ReplicaId, &mut S)
+{
+  /// The seed is taken from the clock, it is printed when a test fails and can be fixed with `seed` (see `test_seed` for the unit tests).
+  pub fn new(test_id: usize, replicas: usize, operation: O) -> Self {
+    assert!(replicas > 0, "at least one replica is needed.");
+    ConvergenceTester {
+      test_id: test_id,
+      replicas: replicas,
+      operation: operation,
+      seed: clock_seed(),
+      steps: 200,
+      merge_orders: 20,
+      phantom_state: PhantomData
+    }
+  }
+
+  pub fn seed(mut self, seed: u64) -> Self {
+    self.seed = seed;
+    self
+  }
+
+  pub fn steps(mut self, steps: usize) -> Self {
+    self.steps = steps;
+    self
+  }
+
+  pub fn merge_orders(mut self, merge_orders: usize) -> Self {
+    self.merge_orders = merge_orders;
+    self
+  }
+
+  pub fn test_all(mut self) {
+    let mut rng = Rng::new(self.seed);
+    let mut states = vec![S::bot(); self.replicas];
+    let mut history = vec![];
+    for step in 0..self.steps {
+      let replica = rng.below(self.replicas as u64) as usize;
+      if rng.below(4) == 0 {
+        let from = rng.below(self.replicas as u64) as usize;
+        let incoming = states[from].clone();
+        states[replica].join_assign(incoming);
+      }
+      else {
+        let before = states[replica].clone();
+        (self.operation)(&mut rng, replica, &mut states[replica]);
+        assert!(states[replica].entail(&before) == True,
+          "[{}] update of the replica {} at the step {} is not inflationary (seed {}).\n before = {:?}\n after = {:?}",
+          self.test_id, replica, step, self.seed, before, states[replica]);
+      }
+      history.push(states[replica].clone());
+    }
+    let merged = states.iter().cloned().fold(S::bot(), S::join);
+    self.test_merge_orders(&mut rng, &states, &merged);
+    self.test_gossip(&mut rng, states, &merged);
+    self.test_semilattice(&mut rng, history);
+  }
+
+  /// Merges random pairs of states until only one remains, some states are duplicated to check idempotency.
+  fn test_merge_orders(&self, rng: &mut Rng, states: &[S], merged: &S) {
+    for order in 0..self.merge_orders {
+      let mut pool = states.to_vec();
+      for _ in 0..self.replicas / 2 + 1 {
+        let duplicate = rng.below(states.len() as u64) as usize;
+        pool.push(states[duplicate].clone());
+      }
+      while pool.len() > 1 {
+        let a = pool.swap_remove(rng.below(pool.len() as u64) as usize);
+        let b = pool.swap_remove(rng.below(pool.len() as u64) as usize);
+        pool.push(a.join(b));
+      }
+      assert!(pool[0] == *merged,
+        "[{}] the merge order {} does not converge (seed {}).\n expected = {:?}\n obtained = {:?}",
+        self.test_id, order, self.seed, merged, pool[0]);
+    }
+  }
+
+  /// The replicas send their states to each other, one message at a time and in a random order, until no message changes a state.
+  /// Every pair of replicas exchanges messages in each round, thus the quiescence must be reached within `replicas + 1` rounds.
+  /// All the replicas must then be equal to each other and to the join of their states before the gossip.
+  fn test_gossip(&self, rng: &mut Rng, mut states: Vec<S>, merged: &S) {
+    let mut messages: Vec<(ReplicaId, ReplicaId)> = (0..self.replicas)
+      .flat_map(|from| (0..self.replicas).filter(move |&to| to != from).map(move |to| (from, to)))
+      .collect();
+    let mut rounds = 0;
+    let mut changed = true;
+    while changed {
+      assert!(rounds <= self.replicas,
+        "[{}] the gossip does not reach quiescence after {} rounds (seed {}).",
+        self.test_id, rounds, self.seed);
+      rng.shuffle(&mut messages);
+      changed = false;
+      for &(from, to) in &messages {
+        let incoming = states[from].clone();
+        changed |= states[to].join_assign(incoming);
+      }
+      rounds += 1;
+    }
+    for (replica, state) in states.iter().enumerate() {
+      assert!(*state == states[0] && *state == *merged,
+        "[{}] the replica {} does not converge after the gossip (seed {}).\n expected = {:?}\n replica 0 = {:?}\n obtained = {:?}",
+        self.test_id, replica, self.seed, merged, states[0], state);
+    }
+  }
+
+  fn test_semilattice(&self, rng: &mut Rng, mut history: Vec<S>) {
+    let data_a = history.clone();
+    rng.shuffle(&mut history);
+    JoinSemilatticeTester::from_data(self.test_id, data_a, history).test_all();
+  }
+}
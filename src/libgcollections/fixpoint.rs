@@ -0,0 +1,386 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Computation of least fixpoints of monotone functions over lattices, only the join and the bottom element are needed hence any `BoundedJoinSemilattice` can be used.
+//!
+//! * `Solver::kleene` computes the least fixpoint of a single function by iterating it from `bot`.
+//! * `Solver::chaotic` computes the least solution of a system of equations `x_i = f_i(x_0, ..., x_n)` with a worklist: an equation is only evaluated again when one of the variables it reads has changed.
+//!
+//! The iteration stops when the current value entails the value computed by the function, which means it is stable.
+//! Every step joins the new value with the current one, thus the iteration is increasing even if the functions are not extensive.
+//! The number of steps can be bounded, in which case the result reports if the cap was hit before convergence.
+//!
+//! Over lattices with infinite ascending chains, `Solver::kleene_widening` and `Solver::chaotic_widening` ensure termination by using the widening instead of the join, at every step for the former and only on the variables declared as loop heads (with `EquationSystem::widen_at`) for the latter.
+//!
+//! ```rust
+//! use gcollections::*;
+//! use gcollections::ops::*;
+//! use gcollections::fixpoint::*;
+//!
+//! // Nodes reachable from the node 0 in the graph 0 -> 1 -> 2 -> 0, 3 -> 0.
+//! let edges = vec![(0, 1), (1, 2), (2, 0), (3, 0)];
+//! let result = Solver::new().kleene(|reached: &BTreeSet<usize>| {
+//!   let mut next = BTreeSet::singleton(0);
+//!   for &(from, to) in &edges {
+//!     if reached.contains(&from) {
+//!       next.insert(to);
+//!     }
+//!   }
+//!   next
+//! });
+//! assert_eq!(result.status, Status::Converged);
+//! assert_eq!(result.value, BTreeSet::wrap(vec![0, 1, 2].into_iter().collect()));
+//! ```
+
+use ops::*;
+use queue::*;
+use wrappers::vector_deque::*;
+use trilean::SKleene::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Status
+{
+  Converged,
+  CapReached
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Fixpoint<L>
+{
+  pub value: L,
+  /// Number of evaluations of the functions.
+  pub iterations: usize,
+  pub status: Status
+}
+
+type Equation<'a, L> = Box<dyn Fn(&[L]) -> L + 'a>;
+
+/// A system of equations where the variable `x_i` is defined by the `i`-th equation.
+pub struct EquationSystem<'a, L>
+{
+  equations: Vec<Equation<'a, L>>,
+  /// `influences[i]` are the equations reading the variable `x_i`.
+  influences: Vec<Vec<usize>>,
+  loop_heads: Vec<usize>
+}
+
+impl<'a, L> EquationSystem<'a, L>
+{
+  pub fn new() -> Self {
+    EquationSystem {
+      equations: vec![],
+      influences: vec![],
+      loop_heads: vec![]
+    }
+  }
+
+  /// Adds the equation of a new variable and returns its index.
+  /// The function `f` must be monotone and only read the variables in `reads`, which can refer to variables not yet added.
+  /// Since the equations can be mutually recursive, the solvers check that every variable in `reads` has been added before solving the system.
+  pub fn add<F>(&mut self, reads: Vec<usize>, f: F) -> usize where
+   F: Fn(&[L]) -> L + 'a
+  {
+    let var = self.equations.len();
+    self.equations.push(Box::new(f));
+    for read in reads {
+      if read >= self.influences.len() {
+        self.influences.resize(read + 1, vec![]);
+      }
+      self.influences[read].push(var);
+    }
+    var
+  }
+
+  /// Declares `var` as a loop head: its successive values are combined with the widening instead of the join.
+  /// Every cycle in the dependencies between the equations should contain at least one loop head.
+  /// Panics if the equation of `var` has not been added.
+  pub fn widen_at(&mut self, var: usize) {
+    assert!(var < self.len(), "The loop head {} is not a variable of the system ({} variables).", var, self.len());
+    self.loop_heads.push(var);
+  }
+
+  /// Panics if an equation reads a variable which has not been added.
+  fn check_reads(&self) {
+    if let Some(var) = (self.len()..self.influences.len()).find(|&var| !self.influences[var].is_empty()) {
+      panic!("The variable {} is read by the equation {} but it has not been added ({} variables).",
+        var, self.influences[var][0], self.len());
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.equations.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.equations.is_empty()
+  }
+}
+
+impl<'a, L> Default for EquationSystem<'a, L>
+{
+  fn default() -> Self {
+    EquationSystem::new()
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Solver
+{
+  max_iterations: Option<usize>
+}
+
+impl Solver
+{
+  /// The iteration only stops when a fixpoint is reached.
+  pub fn new() -> Solver {
+    Solver {
+      max_iterations: None
+    }
+  }
+
+  /// The iteration stops after at most `max_iterations` evaluations of the functions.
+  pub fn bounded(max_iterations: usize) -> Solver {
+    Solver {
+      max_iterations: Some(max_iterations)
+    }
+  }
+
+  fn cap_reached(&self, iterations: usize) -> bool {
+    self.max_iterations.is_some_and(|cap| iterations >= cap)
+  }
+
+  pub fn kleene<L, F>(&self, f: F) -> Fixpoint<L> where
+   L: BoundedJoinSemilattice,
+   F: Fn(&L) -> L
+  {
+    self.kleene_with(f, L::join)
+  }
+
+  pub fn kleene_widening<L, F>(&self, f: F) -> Fixpoint<L> where
+   L: BoundedJoinSemilattice + Widen,
+   F: Fn(&L) -> L
+  {
+    self.kleene_with(f, L::widen)
+  }
+
+  fn kleene_with<L, F, U>(&self, f: F, update: U) -> Fixpoint<L> where
+   L: BoundedJoinSemilattice,
+   F: Fn(&L) -> L,
+   U: Fn(L, L) -> L
+  {
+    let mut value = L::bot();
+    let mut iterations = 0;
+    loop {
+      if self.cap_reached(iterations) {
+        return Fixpoint { value: value, iterations: iterations, status: Status::CapReached };
+      }
+      let next = f(&value);
+      iterations += 1;
+      if value.entail(&next) == True {
+        return Fixpoint { value: value, iterations: iterations, status: Status::Converged };
+      }
+      value = update(value, next);
+    }
+  }
+
+  pub fn chaotic<L>(&self, system: &EquationSystem<L>) -> Fixpoint<Vec<L>> where
+   L: BoundedJoinSemilattice + Clone
+  {
+    self.chaotic_with(system, |_, x, y| x.join_assign(y))
+  }
+
+  pub fn chaotic_widening<L>(&self, system: &EquationSystem<L>) -> Fixpoint<Vec<L>> where
+   L: BoundedJoinSemilattice + Widen + Clone
+  {
+    let mut loop_heads = vec![false; system.len()];
+    for &var in &system.loop_heads {
+      loop_heads[var] = true;
+    }
+    self.chaotic_with(system, |var, x: &mut L, y|
+      if !loop_heads[var] { x.join_assign(y) }
+      else if x.entail(&y) == True { false }
+      else {
+        *x = x.clone().widen(y);
+        true
+      })
+  }
+
+  /// `update(i, x_i, v)` combines the new value `v` into the variable `x_i`, and returns `true` if it has changed.
+  fn chaotic_with<L, U>(&self, system: &EquationSystem<L>, update: U) -> Fixpoint<Vec<L>> where
+   L: BoundedJoinSemilattice + Clone,
+   U: Fn(usize, &mut L, L) -> bool
+  {
+    system.check_reads();
+    let n = system.len();
+    let mut values: Vec<L> = (0..n).map(|_| L::bot()).collect();
+    let mut in_worklist = vec![true; n];
+    let mut worklist: DequeFrontBackQueue<usize> = Queue::empty();
+    for i in 0..n {
+      worklist.insert(i);
+    }
+    let mut iterations = 0;
+    while let Some(i) = worklist.extract() {
+      if self.cap_reached(iterations) {
+        return Fixpoint { value: values, iterations: iterations, status: Status::CapReached };
+      }
+      in_worklist[i] = false;
+      let next = (system.equations[i])(&values);
+      iterations += 1;
+      if update(i, &mut values[i], next) {
+        for &j in system.influences.get(i).into_iter().flatten() {
+          if !in_worklist[j] {
+            in_worklist[j] = true;
+            worklist.insert(j);
+          }
+        }
+      }
+    }
+    Fixpoint { value: values, iterations: iterations, status: Status::Converged }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use wrappers::btree_set::*;
+  use trilean::SKleene;
+  use ops::lattice::test::*;
+
+  fn set(values: &[u32]) -> BTreeSet<u32> {
+    BTreeSet::wrap(values.iter().cloned().collect())
+  }
+
+  fn successors(node: u32) -> Vec<u32> {
+    match node {
+      0 => vec![1],
+      1 => vec![2, 3],
+      2 => vec![0],
+      _ => vec![]
+    }
+  }
+
+  #[test]
+  fn kleene_test() {
+    let result = Solver::new().kleene(|reached: &BTreeSet<u32>| {
+      let mut next = set(&[0]);
+      for node in reached.iter() {
+        next.extend(successors(*node));
+      }
+      next
+    });
+    assert_eq!(result.status, Status::Converged);
+    assert_eq!(result.value, set(&[0, 1, 2, 3]));
+    assert_eq!(result.iterations, 4);
+  }
+
+  #[test]
+  fn kleene_cap_test() {
+    let result = Solver::bounded(10).kleene(|naturals: &BTreeSet<u32>| {
+      let mut next = set(&[0]);
+      next.extend(naturals.iter().map(|x| x + 1));
+      next
+    });
+    assert_eq!(result.status, Status::CapReached);
+    assert_eq!(result.iterations, 10);
+    assert_eq!(result.value, set(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]));
+  }
+
+  #[test]
+  fn chaotic_test() {
+    // Reachable nodes from each node of the graph: x_i = {i} U x_succ(i).
+    let mut system = EquationSystem::new();
+    for node in 0..4 {
+      let succ = successors(node);
+      system.add(succ.iter().map(|x| *x as usize).collect(), move |values: &[BTreeSet<u32>]| {
+        let mut reached = set(&[node]);
+        for s in &succ {
+          reached = reached.join(values[*s as usize].clone());
+        }
+        reached
+      });
+    }
+    let result = Solver::new().chaotic(&system);
+    assert_eq!(result.status, Status::Converged);
+    assert_eq!(result.value, vec![set(&[0, 1, 2, 3]), set(&[0, 1, 2, 3]), set(&[0, 1, 2, 3]), set(&[3])]);
+
+    let result = Solver::bounded(2).chaotic(&system);
+    assert_eq!(result.status, Status::CapReached);
+    assert_eq!(result.iterations, 2);
+  }
+
+  /// Upper bound of a counter, the height of this lattice is `u64::MAX`.
+  #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+  struct UpperBound(u64);
+
+  impl Join for UpperBound {
+    fn join(self, other: UpperBound) -> UpperBound { UpperBound(self.0.max(other.0)) }
+  }
+
+  impl Meet for UpperBound {
+    fn meet(self, other: UpperBound) -> UpperBound { UpperBound(self.0.min(other.0)) }
+  }
+
+  impl Entailment for UpperBound {
+    fn entail(&self, other: &UpperBound) -> SKleene { SKleene::from_bool(self.0 >= other.0) }
+  }
+
+  impl Bot for UpperBound {
+    fn bot() -> UpperBound { UpperBound(0) }
+  }
+
+  impl Widen for UpperBound {
+    fn widen(self, other: UpperBound) -> UpperBound {
+      if other.0 > self.0 { UpperBound(u64::MAX) } else { self }
+    }
+  }
+
+  impl Narrow for UpperBound {}
+
+  #[test]
+  fn widening_test() {
+    let tester = WideningTester::new(0, vec![UpperBound(0), UpperBound(3)], vec![UpperBound(5), UpperBound(3)]);
+    tester.test_chain(100, 1, |i| UpperBound(i as u64));
+    tester.test_all();
+
+    let increment = |x: &UpperBound| UpperBound(x.0.saturating_add(1));
+    let result = Solver::bounded(1000).kleene(increment);
+    assert_eq!(result.status, Status::CapReached);
+    let result = Solver::new().kleene_widening(increment);
+    assert_eq!(result.status, Status::Converged);
+    assert_eq!(result.value, UpperBound(u64::MAX));
+
+    // x_0 = 10, x_1 = x_0 join (x_1 + 1), x_2 = x_1
+    let mut system = EquationSystem::new();
+    system.add(vec![], |_: &[UpperBound]| UpperBound(10));
+    let head = system.add(vec![0, 1], |x: &[UpperBound]| x[0].join(increment(&x[1])));
+    system.add(vec![1], |x: &[UpperBound]| x[1]);
+    system.widen_at(head);
+    let result = Solver::bounded(1000).chaotic(&system);
+    assert_eq!(result.status, Status::CapReached);
+    let result = Solver::new().chaotic_widening(&system);
+    assert_eq!(result.status, Status::Converged);
+    assert_eq!(result.value, vec![UpperBound(10), UpperBound(u64::MAX), UpperBound(u64::MAX)]);
+  }
+
+  #[test]
+  #[should_panic(expected = "The loop head 1 is not a variable of the system")]
+  fn widen_at_unknown_variable_test() {
+    let mut system = EquationSystem::new();
+    system.add(vec![], |_: &[UpperBound]| UpperBound(10));
+    system.widen_at(1);
+  }
+
+  #[test]
+  #[should_panic(expected = "The variable 2 is read by the equation 1 but it has not been added")]
+  fn unknown_read_test() {
+    let mut system = EquationSystem::new();
+    system.add(vec![1], |x: &[UpperBound]| x[1]);
+    system.add(vec![0, 2], |x: &[UpperBound]| x[0].join(x[2]));
+    Solver::new().chaotic(&system);
+  }
+}
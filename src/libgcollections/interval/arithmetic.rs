@@ -0,0 +1,305 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Interval arithmetic: the result of `x op y` is the smallest interval containing `a op b` for every `a` in `x` and `b` in `y`.
+//!
+//! The bounds are computed on `i128`, where the operations on the bounds of an interval do not overflow (or saturate far beyond the bounds of `Width`).
+//! When the exact result does not fit in `[Width::min_bound()..Width::max_bound()]`, the operation overflows and the result is `Interval::whole()`.
+//! Clamping the bounds instead would be unsound: the overflowing concrete results are not represented by the bounds of `Width`.
+//! For example, `Interval::new(0u8, 1) - 1` is `Interval::whole()`.
+//!
+//! The division and remainder ignore the divisor `0`, thus `x / Interval::singleton(0)` is empty.
+//! The division is truncated toward zero and the remainder has the sign of the dividend, as the primitive operations.
+//! Every operation is also defined between an interval and a scalar, on both sides.
+//!
+//! ```rust
+//! use gcollections::ops::*;
+//! use gcollections::interval::Interval;
+//!
+//! let x = Interval::new(-2, 3);
+//! assert_eq!(x + 1, Interval::new(-1, 4));
+//! assert_eq!(x * x, Interval::new(-6, 9));
+//! assert_eq!(10 / x, Interval::new(-10, 10));
+//! assert_eq!(Interval::new(100i8, 110) + 10, Interval::new(110, 120));
+//! assert_eq!(Interval::new(100i8, 120) + 10, Interval::whole());
+//! ```
+
+use interval::*;
+use std::cmp::{min, max};
+use std::ops::*;
+
+/// Bounds of a non-empty interval.
+type Wide = (i128, i128);
+
+fn hull(values: &[i128]) -> Option<Wide> {
+  let lb = values.iter().cloned().min();
+  let ub = values.iter().cloned().max();
+  lb.and_then(|lb| ub.map(|ub| (lb, ub)))
+}
+
+fn wide_add(x: Wide, y: Wide) -> Option<Wide> {
+  Some((x.0.saturating_add(y.0), x.1.saturating_add(y.1)))
+}
+
+fn wide_sub(x: Wide, y: Wide) -> Option<Wide> {
+  Some((x.0.saturating_sub(y.1), x.1.saturating_sub(y.0)))
+}
+
+fn wide_mul(x: Wide, y: Wide) -> Option<Wide> {
+  hull(&[x.0.saturating_mul(y.0), x.0.saturating_mul(y.1),
+         x.1.saturating_mul(y.0), x.1.saturating_mul(y.1)])
+}
+
+/// The divisor is split in its negative and positive parts, the division is monotone on each of them.
+fn wide_div(x: Wide, y: Wide) -> Option<Wide> {
+  let mut quotients = vec![];
+  for part in &[(y.0, min(y.1, -1)), (max(y.0, 1), y.1)] {
+    if part.0 <= part.1 {
+      quotients.extend_from_slice(&[x.0 / part.0, x.0 / part.1, x.1 / part.0, x.1 / part.1]);
+    }
+  }
+  hull(&quotients)
+}
+
+/// The remainder is exact when the dividend is smaller than every divisor in absolute value.
+/// Otherwise, its absolute value is below the largest divisor and the dividend, and it has the sign of the dividend.
+fn wide_rem(x: Wide, y: Wide) -> Option<Wide> {
+  let largest = max(y.0.abs(), y.1.abs());
+  let smallest =
+    if y.0 > 0 { y.0 }
+    else if y.1 < 0 { -y.1 }
+    else { 1 };
+  if largest == 0 { None }
+  else if (x.0 >= 0 && x.1 < smallest) || (x.1 <= 0 && x.0 > -smallest) { Some(x) }
+  else {
+    let lb = if x.0 < 0 { max(x.0, 1 - largest) } else { 0 };
+    let ub = if x.1 > 0 { min(x.1, largest - 1) } else { 0 };
+    Some((lb, ub))
+  }
+}
+
+macro_rules! binary_arithmetic_impl
+{
+  ( $t:ty; $( $op:ident, $m:ident, $wide:ident );* ) =>
+  {$(
+    impl $op for Interval<$t>
+    {
+      type Output = Interval<$t>;
+
+      fn $m(self, other: Interval<$t>) -> Interval<$t> {
+        Interval::<$t>::from_wide(self.wide_bounds().and_then(|x|
+          other.wide_bounds().and_then(|y| $wide(x, y))))
+      }
+    }
+
+    impl $op<$t> for Interval<$t>
+    {
+      type Output = Interval<$t>;
+
+      fn $m(self, other: $t) -> Interval<$t> {
+        let y = other as i128;
+        Interval::<$t>::from_wide(self.wide_bounds().and_then(|x| $wide(x, (y, y))))
+      }
+    }
+
+    impl $op<Interval<$t>> for $t
+    {
+      type Output = Interval<$t>;
+
+      fn $m(self, other: Interval<$t>) -> Interval<$t> {
+        let x = self as i128;
+        Interval::<$t>::from_wide(other.wide_bounds().and_then(|y| $wide((x, x), y)))
+      }
+    }
+  )*}
+}
+
+macro_rules! interval_arithmetic_impl
+{
+  ( $( $t:ty ),* ) =>
+  {$(
+    impl Interval<$t>
+    {
+      fn wide_bounds(&self) -> Option<Wide> {
+        if self.is_empty() { None }
+        else { Some((self.lower() as i128, self.upper() as i128)) }
+      }
+
+      /// `Interval::whole()` if `wide` does not fit in the bounds of `Width`.
+      fn from_wide(wide: Option<Wide>) -> Interval<$t> {
+        match wide {
+          None => Interval::empty(),
+          Some((lb, ub)) =>
+            if lb < <$t as Width>::min_bound() as i128 || ub > <$t as Width>::max_bound() as i128 {
+              Interval::whole()
+            }
+            else {
+              Interval::new(lb as $t, ub as $t)
+            }
+        }
+      }
+    }
+
+    binary_arithmetic_impl!($t;
+      Add, add, wide_add;
+      Sub, sub, wide_sub;
+      Mul, mul, wide_mul;
+      Div, div, wide_div;
+      Rem, rem, wide_rem);
+  )*}
+}
+
+interval_arithmetic_impl!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+
+macro_rules! interval_neg_impl
+{
+  ( $( $t:ty ),* ) =>
+  {$(
+    /// The bounds of a signed interval are symmetric, thus the negation is exact.
+    impl Neg for Interval<$t>
+    {
+      type Output = Interval<$t>;
+
+      fn neg(self) -> Interval<$t> {
+        if self.is_empty() { self }
+        else { Interval::new(-self.upper(), -self.lower()) }
+      }
+    }
+  )*}
+}
+
+interval_neg_impl!(i8, i16, i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ops::lattice::test::{Rng, test_seed};
+
+  fn i(lb: i32, ub: i32) -> Interval<i32> {
+    Interval::new(lb, ub)
+  }
+
+  #[test]
+  fn arithmetics_test() {
+    let empty = Interval::<i32>::empty();
+    let cases = vec![
+      // x,         y,         x+y,        x-y,        x*y,         x/y,        x%y
+      (empty,       i(1, 2),   empty,      empty,      empty,       empty,      empty),
+      (i(1, 2),     empty,     empty,      empty,      empty,       empty,      empty),
+      (i(1, 2),     i(3, 4),   i(4, 6),    i(-3, -1),  i(3, 8),     i(0, 0),    i(1, 2)),
+      (i(-2, 3),    i(-1, 4),  i(-3, 7),   i(-6, 4),   i(-8, 12),   i(-3, 3),   i(-2, 3)),
+      (i(10, 20),   i(0, 0),   i(10, 20),  i(10, 20),  i(0, 0),     empty,      empty),
+      (i(10, 20),   i(-5, 0),  i(5, 20),   i(10, 25),  i(-100, 0),  i(-20, -2), i(0, 4)),
+      (i(-20, -10), i(3, 3),   i(-17, -7), i(-23, -13), i(-60, -30), i(-6, -3), i(-2, 0))
+    ];
+    for (x, y, add, sub, mul, div, rem) in cases {
+      assert_eq!(x + y, add, "{} + {}", x, y);
+      assert_eq!(x - y, sub, "{} - {}", x, y);
+      assert_eq!(x * y, mul, "{} * {}", x, y);
+      assert_eq!(x / y, div, "{} / {}", x, y);
+      assert_eq!(x % y, rem, "{} % {}", x, y);
+    }
+    assert_eq!(-i(-2, 5), i(-5, 2));
+    assert_eq!(-empty, empty);
+  }
+
+  #[test]
+  fn scalar_test() {
+    let x = i(-2, 5);
+    assert_eq!(x + 3, i(1, 8));
+    assert_eq!(3 + x, i(1, 8));
+    assert_eq!(x - 3, i(-5, 2));
+    assert_eq!(3 - x, i(-2, 5));
+    assert_eq!(x * -2, i(-10, 4));
+    assert_eq!(-2 * x, i(-10, 4));
+    assert_eq!(x / 2, i(-1, 2));
+    assert_eq!(20 / x, i(-20, 20));
+    assert_eq!(x % 3, i(-2, 2));
+    assert_eq!(7 % x, i(0, 4));
+    assert_eq!(x / 0, Interval::empty());
+  }
+
+  #[test]
+  fn overflow_test() {
+    assert_eq!(Interval::new(0u8, 1) - 1, Interval::whole());
+    assert_eq!(Interval::new(1u8, 2) - 1, Interval::new(0, 1));
+    assert_eq!(Interval::new(250u8, 254) + 10, Interval::whole());
+    assert_eq!(Interval::new(250u8, 253) + 1, Interval::new(251, 254));
+    assert_eq!(Interval::new(-100i8, 100) * 2, Interval::whole());
+    assert_eq!(Interval::new(-63i8, 63) * 2, Interval::new(-126, 126));
+    assert_eq!(-Interval::<i8>::whole(), Interval::whole());
+    let whole = Interval::<i64>::whole();
+    assert_eq!(whole * whole, whole);
+    assert_eq!(whole - whole, whole);
+    assert_eq!(whole / -1, whole);
+    let whole = Interval::<u64>::whole();
+    assert_eq!(whole * whole, whole);
+    assert_eq!(whole + u64::MAX, whole);
+    assert_eq!(Interval::singleton(0u64) + (u64::MAX - 1), Interval::singleton(u64::MAX - 1));
+  }
+
+  /// Bounds generated around zero and near the bounds of `Width`, where the overflows happen.
+  fn arbitrary_i8(rng: &mut Rng) -> Interval<i8> {
+    let bound = |rng: &mut Rng| match rng.below(4) {
+      0 => 127 - rng.below(10) as i8,
+      1 => -127 + rng.below(10) as i8,
+      _ => rng.below(17) as i8 - 8
+    };
+    let (a, b) = (bound(rng), bound(rng));
+    Interval::new(min(a, b), max(a, b))
+  }
+
+  fn values(x: &Interval<i8>) -> Vec<i128> {
+    if x.is_empty() { vec![] }
+    else { (x.lower() as i128..x.upper() as i128 + 1).collect() }
+  }
+
+  /// Checks that the result of the interval operation is the hull of the concrete results, or only contains them if `exact` is false.
+  /// If a concrete result does not fit in the bounds of `Width`, the result must be `Interval::whole()`.
+  /// The concrete operation returns `None` when it is undefined.
+  fn check_operation<F>(seed: u64, name: &str, x: Interval<i8>, y: Interval<i8>, result: Interval<i8>, exact: bool, op: F) where
+   F: Fn(i128, i128) -> Option<i128>
+  {
+    let mut concrete = Interval::empty();
+    let mut overflow = false;
+    for a in values(&x) {
+      for b in values(&y).into_iter().filter(|&b| op(a, b).is_some()) {
+        let v = op(a, b).unwrap();
+        if !(-127..=127).contains(&v) {
+          overflow = true;
+          continue;
+        }
+        let v = v as i8;
+        assert!(result.contains(&v), "[seed {}] {} {} {} = {} does not contain {} {} {} = {}",
+          seed, x, name, y, result, a, name, b, v);
+        concrete = concrete.union(&Interval::singleton(v));
+      }
+    }
+    if overflow {
+      assert_eq!(result, Interval::whole(), "[seed {}] {} {} {} overflows but is not the whole interval", seed, x, name, y);
+    }
+    else if exact {
+      assert_eq!(result, concrete, "[seed {}] {} {} {} is not the hull of the concrete results", seed, x, name, y);
+    }
+  }
+
+  #[test]
+  fn soundness_test() {
+    let seed = test_seed(0);
+    let mut rng = Rng::new(seed);
+    for _ in 0..300 {
+      let (x, y) = (arbitrary_i8(&mut rng), arbitrary_i8(&mut rng));
+      check_operation(seed, "+", x, y, x + y, true, |a, b| Some(a + b));
+      check_operation(seed, "-", x, y, x - y, true, |a, b| Some(a - b));
+      check_operation(seed, "*", x, y, x * y, true, |a, b| Some(a * b));
+      check_operation(seed, "/", x, y, x / y, true, |a, b| a.checked_div(b));
+      check_operation(seed, "%", x, y, x % y, false, |a, b| a.checked_rem(b));
+      check_operation(seed, "neg", x, Interval::singleton(0), -x, true, |a, _| Some(-a));
+    }
+  }
+}
@@ -0,0 +1,723 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Closed intervals `[lower..upper]` over floating-point bounds, which represent every real number between the bounds.
+//!
+//! The bounds can be infinite, `FloatInterval::whole()` is `[-inf..inf]`.
+//! `NaN` is never a bound: a `NaN` lower bound is replaced by `-inf` and a `NaN` upper bound by `inf`, which is sound since a `NaN` bound is unknown.
+//! `NaN` is not contained in any interval, and the operations with a `NaN` scalar return `FloatInterval::whole()`.
+//! The bound `-0.0` is normalized to `0.0`.
+//!
+//! The arithmetic is rounded outward: the lower bound of a result is rounded toward `-inf` and the upper bound toward `inf`, thus the result contains the exact result over the reals.
+//! A bound is only moved to the next representable value when the rounded operation is not exact, which is detected with error-free transformations.
+//! The strict shrinking operations move to the next representable value (see `ops::bounded::Successor`, implemented for `f32` and `f64`).
+//!
+//! ```rust
+//! use gcollections::ops::*;
+//! use gcollections::interval::FloatInterval;
+//!
+//! let tenth = FloatInterval::singleton(0.1) + FloatInterval::singleton(0.2);
+//! assert!(tenth.lower() < tenth.upper());
+//! assert!(tenth.contains(&0.30000000000000004));
+//! assert_eq!(FloatInterval::new(1.0, 2.0) / FloatInterval::new(0.0, 4.0), FloatInterval::new(0.25, f64::INFINITY));
+//! assert_eq!(FloatInterval::new(0.0, 1.0).strict_shrink_left(0.0).lower(), 5e-324);
+//! ```
+
+use kind::*;
+use ops::*;
+use wrappers::powerset::subset_entailment;
+use trilean::SKleene;
+use num_traits::Float;
+use std::ops::*;
+use std::fmt::{Formatter, Display, Error};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FloatInterval<F>
+{
+  lb: F,
+  ub: F
+}
+
+/// The bounds are never `NaN`, thus the equality is total.
+impl<F: Float> Eq for FloatInterval<F> {}
+
+impl<F> FloatInterval<F> where
+ F: Float + Successor
+{
+  /// The interval `[lb..ub]`, it is empty if `lb > ub`.
+  pub fn new(lb: F, ub: F) -> FloatInterval<F> {
+    let lb = if lb.is_nan() { F::neg_infinity() } else { lb + F::zero() };
+    let ub = if ub.is_nan() { F::infinity() } else { ub + F::zero() };
+    if lb > ub {
+      FloatInterval::empty()
+    }
+    else {
+      FloatInterval {
+        lb: lb,
+        ub: ub
+      }
+    }
+  }
+
+  pub fn whole() -> FloatInterval<F> {
+    FloatInterval::new(F::neg_infinity(), F::infinity())
+  }
+
+  fn is_empty_interval(&self) -> bool {
+    self.lb > self.ub
+  }
+}
+
+impl<F> Collection for FloatInterval<F> {
+  type Item = F;
+}
+
+impl<F> Bounded for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn lower(&self) -> F {
+    debug_assert!(!self.is_empty_interval(), "Cannot access the lower bound of an empty interval.");
+    self.lb
+  }
+
+  fn upper(&self) -> F {
+    debug_assert!(!self.is_empty_interval(), "Cannot access the upper bound of an empty interval.");
+    self.ub
+  }
+}
+
+/// Shrinking by `NaN` leaves the interval unchanged.
+impl<F> ShrinkLeft for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn shrink_left(&self, lb: F) -> FloatInterval<F> {
+    if self.is_empty_interval() || lb.is_nan() { *self }
+    else { FloatInterval::new(self.lb.max(lb), self.ub) }
+  }
+}
+
+impl<F> ShrinkRight for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn shrink_right(&self, ub: F) -> FloatInterval<F> {
+    if self.is_empty_interval() || ub.is_nan() { *self }
+    else { FloatInterval::new(self.lb, self.ub.min(ub)) }
+  }
+}
+
+/// The bound moves to the next representable value, the interval becomes empty if there is none.
+impl<F> StrictShrinkLeft for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn strict_shrink_left(&self, lb: F) -> FloatInterval<F> {
+    match lb.successor() {
+      Some(lb) => self.shrink_left(lb),
+      None => FloatInterval::empty()
+    }
+  }
+}
+
+/// The bound moves to the previous representable value, the interval becomes empty if there is none.
+impl<F> StrictShrinkRight for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn strict_shrink_right(&self, ub: F) -> FloatInterval<F> {
+    match ub.predecessor() {
+      Some(ub) => self.shrink_right(ub),
+      None => FloatInterval::empty()
+    }
+  }
+}
+
+impl<F> Empty for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn empty() -> FloatInterval<F> {
+    FloatInterval {
+      lb: F::infinity(),
+      ub: F::neg_infinity()
+    }
+  }
+}
+
+impl<F> Singleton for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn singleton(value: F) -> FloatInterval<F> {
+    FloatInterval::new(value, value)
+  }
+}
+
+/// The interval contains uncountably many reals, so it has no `Cardinality`.
+impl<F> IsEmpty for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn is_empty(&self) -> bool {
+    self.is_empty_interval()
+  }
+}
+
+impl<F> IsSingleton for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn is_singleton(&self) -> bool {
+    self.lb == self.ub
+  }
+}
+
+impl<F> Contains for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn contains(&self, value: &F) -> bool {
+    self.lb <= *value && *value <= self.ub
+  }
+}
+
+impl<F> Intersection for FloatInterval<F> where
+ F: Float + Successor
+{
+  type Output = FloatInterval<F>;
+
+  fn intersection(&self, other: &FloatInterval<F>) -> FloatInterval<F> {
+    FloatInterval::new(self.lb.max(other.lb), self.ub.min(other.ub))
+  }
+}
+
+/// The hull of the union.
+impl<F> Union for FloatInterval<F> where
+ F: Float + Successor
+{
+  type Output = FloatInterval<F>;
+
+  fn union(&self, other: &FloatInterval<F>) -> FloatInterval<F> {
+    if self.is_empty_interval() { *other }
+    else if other.is_empty_interval() { *self }
+    else { FloatInterval::new(self.lb.min(other.lb), self.ub.max(other.ub)) }
+  }
+}
+
+/// The hull of the difference, the bounds of `other` are excluded with the strict shrinking operations.
+impl<F> Difference for FloatInterval<F> where
+ F: Float + Successor
+{
+  type Output = FloatInterval<F>;
+
+  fn difference(&self, other: &FloatInterval<F>) -> FloatInterval<F> {
+    if self.is_disjoint(other) { *self }
+    else if other.lb <= self.lb { self.strict_shrink_left(other.ub) }
+    else if other.ub >= self.ub { self.strict_shrink_right(other.lb) }
+    else { *self }
+  }
+}
+
+impl<F> SymmetricDifference for FloatInterval<F> where
+ F: Float + Successor
+{
+  type Output = FloatInterval<F>;
+
+  fn symmetric_difference(&self, other: &FloatInterval<F>) -> FloatInterval<F> {
+    self.difference(other).union(&other.difference(self))
+  }
+}
+
+/// The hull of the complement in `FloatInterval::whole()`.
+impl<F> Complement for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn complement(&self) -> FloatInterval<F> {
+    FloatInterval::whole().difference(self)
+  }
+}
+
+impl<F> Disjoint for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn is_disjoint(&self, other: &FloatInterval<F>) -> bool {
+    self.is_empty_interval() || other.is_empty_interval() ||
+    self.ub < other.lb || other.ub < self.lb
+  }
+}
+
+impl<F> Overlap for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn overlap(&self, other: &FloatInterval<F>) -> bool {
+    !self.is_disjoint(other)
+  }
+}
+
+impl<F> Subset for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn is_subset(&self, other: &FloatInterval<F>) -> bool {
+    self.is_empty_interval() ||
+    (other.lb <= self.lb && self.ub <= other.ub)
+  }
+}
+
+impl<F> ProperSubset for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn is_proper_subset(&self, other: &FloatInterval<F>) -> bool {
+    self.is_subset(other) && self != other
+  }
+}
+
+impl<F> Display for FloatInterval<F> where
+ F: Display + Float + Successor
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    if self.is_empty_interval() {
+      formatter.write_str("{}")
+    }
+    else {
+      write!(formatter, "[{}..{}]", self.lb, self.ub)
+    }
+  }
+}
+
+impl<F> Join for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn join(self, other: FloatInterval<F>) -> FloatInterval<F> {
+    self.union(&other)
+  }
+}
+
+impl<F> Meet for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn meet(self, other: FloatInterval<F>) -> FloatInterval<F> {
+    self.intersection(&other)
+  }
+}
+
+impl<F> Entailment for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn entail(&self, other: &FloatInterval<F>) -> SKleene {
+    subset_entailment(self, other)
+  }
+}
+
+impl<F> Top for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn top() -> FloatInterval<F> {
+    FloatInterval::whole()
+  }
+}
+
+impl<F> Bot for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn bot() -> FloatInterval<F> {
+    FloatInterval::empty()
+  }
+}
+
+/// A bound which is not stable jumps to the infinity.
+impl<F> Widen for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn widen(self, other: FloatInterval<F>) -> FloatInterval<F> {
+    if self.is_empty_interval() { other }
+    else if other.is_empty_interval() { self }
+    else {
+      let lb = if other.lb < self.lb { F::neg_infinity() } else { self.lb };
+      let ub = if other.ub > self.ub { F::infinity() } else { self.ub };
+      FloatInterval::new(lb, ub)
+    }
+  }
+}
+
+/// Only the infinite bounds are refined.
+impl<F> Narrow for FloatInterval<F> where
+ F: Float + Successor
+{
+  fn narrow(self, other: FloatInterval<F>) -> FloatInterval<F> {
+    if self.is_empty_interval() || other.is_empty_interval() { FloatInterval::empty() }
+    else {
+      let lb = if self.lb == F::neg_infinity() { other.lb } else { self.lb };
+      let ub = if self.ub == F::infinity() { other.ub } else { self.ub };
+      FloatInterval::new(lb, ub)
+    }
+  }
+}
+
+// Outward rounding of the operations on the bounds, `down` selects the rounding toward `-inf` and otherwise toward `inf`.
+// `error` is the difference between the exact result and the rounded one `r`, only its sign matters.
+// The bound is moved unconditionally if `r` is not finite or if the error is unknown (`NaN`).
+
+fn round<F: Float + Successor>(r: F, error: F, down: bool) -> F {
+  let unknown = !r.is_finite() || error.is_nan();
+  if down && (unknown || error < F::zero()) { r.predecessor().unwrap_or(r) }
+  else if !down && (unknown || error > F::zero()) { r.successor().unwrap_or(r) }
+  else { r }
+}
+
+/// Infinity of the rounding direction.
+fn outward<F: Float>(down: bool) -> F {
+  if down { F::neg_infinity() } else { F::infinity() }
+}
+
+/// The addition is exact when an operand is infinite, and `inf - inf` is undefined.
+/// Otherwise, the error is computed with the TwoSum algorithm, it is exact even for subnormal results.
+fn add_round<F: Float + Successor>(a: F, b: F, down: bool) -> F {
+  let s = a + b;
+  if a.is_infinite() || b.is_infinite() {
+    if s.is_nan() { outward(down) } else { s }
+  }
+  else if s.is_infinite() {
+    // Overflow: the exact result is finite.
+    if down && s > F::zero() { F::max_value() }
+    else if !down && s < F::zero() { F::min_value() }
+    else { s }
+  }
+  else {
+    let bb = s - a;
+    round(s, (a - (s - bb)) + (b - bb), down)
+  }
+}
+
+/// The product with zero is zero, even with an infinite operand.
+/// The error is computed with a fused multiply-add, it is unknown if the product underflows.
+fn mul_round<F: Float + Successor>(a: F, b: F, down: bool) -> F {
+  let p = a * b;
+  if a == F::zero() || b == F::zero() || a.is_infinite() || b.is_infinite() { p + F::zero() }
+  else if p.abs() < F::min_positive_value() { round(p, F::nan(), down) }
+  else { round(p, a.mul_add(b, -p), down) }
+}
+
+/// The quotient of two infinities is undefined but its sign is known.
+/// Otherwise, the error of `q = a / b` has the sign of `(a - q * b) / b`, it is unknown if the quotient underflows.
+fn div_round<F: Float + Successor>(a: F, b: F, down: bool) -> F {
+  let q = a / b;
+  if a.is_infinite() && b.is_infinite() {
+    let positive = (a > F::zero()) == (b > F::zero());
+    if positive == down { F::zero() } else { outward(down) }
+  }
+  else if a.is_infinite() || b.is_infinite() { q }
+  else if q.abs() < F::min_positive_value() { round(q, F::nan(), down) }
+  else {
+    let remainder = (-q).mul_add(b, a);
+    round(q, if b < F::zero() { -remainder } else { remainder }, down)
+  }
+}
+
+impl<F> Add for FloatInterval<F> where
+ F: Float + Successor
+{
+  type Output = FloatInterval<F>;
+
+  fn add(self, other: FloatInterval<F>) -> FloatInterval<F> {
+    if self.is_empty_interval() || other.is_empty_interval() { FloatInterval::empty() }
+    else { FloatInterval::new(add_round(self.lb, other.lb, true), add_round(self.ub, other.ub, false)) }
+  }
+}
+
+impl<F> Neg for FloatInterval<F> where
+ F: Float + Successor
+{
+  type Output = FloatInterval<F>;
+
+  fn neg(self) -> FloatInterval<F> {
+    if self.is_empty_interval() { self }
+    else { FloatInterval::new(-self.ub, -self.lb) }
+  }
+}
+
+impl<F> Sub for FloatInterval<F> where
+ F: Float + Successor
+{
+  type Output = FloatInterval<F>;
+
+  fn sub(self, other: FloatInterval<F>) -> FloatInterval<F> {
+    self + (-other)
+  }
+}
+
+impl<F> Mul for FloatInterval<F> where
+ F: Float + Successor
+{
+  type Output = FloatInterval<F>;
+
+  fn mul(self, other: FloatInterval<F>) -> FloatInterval<F> {
+    if self.is_empty_interval() || other.is_empty_interval() { FloatInterval::empty() }
+    else {
+      let corners = [(self.lb, other.lb), (self.lb, other.ub), (self.ub, other.lb), (self.ub, other.ub)];
+      let lb = corners.iter().map(|&(a, b)| mul_round(a, b, true)).fold(F::infinity(), F::min);
+      let ub = corners.iter().map(|&(a, b)| mul_round(a, b, false)).fold(F::neg_infinity(), F::max);
+      FloatInterval::new(lb, ub)
+    }
+  }
+}
+
+/// The division by `0` is ignored: a divisor with `0` as bound is treated as the limit toward `0` of the other bound, and a divisor containing `0` strictly gives `whole()` (unless the dividend is `[0..0]`).
+impl<F> Div for FloatInterval<F> where
+ F: Float + Successor
+{
+  type Output = FloatInterval<F>;
+
+  fn div(self, other: FloatInterval<F>) -> FloatInterval<F> {
+    let zero = F::zero();
+    let (inf, neg_inf) = (F::infinity(), F::neg_infinity());
+    let down = |a, b| div_round(a, b, true);
+    let up = |a, b| div_round(a, b, false);
+    let (a, b, c, d) = (self.lb, self.ub, other.lb, other.ub);
+    if self.is_empty_interval() || other.is_empty_interval() || (c == zero && d == zero) {
+      FloatInterval::empty()
+    }
+    else if a == zero && b == zero { self }
+    else if c > zero || d < zero {
+      let corners = [(a, c), (a, d), (b, c), (b, d)];
+      let lb = corners.iter().map(|&(x, y)| down(x, y)).fold(inf, F::min);
+      let ub = corners.iter().map(|&(x, y)| up(x, y)).fold(neg_inf, F::max);
+      FloatInterval::new(lb, ub)
+    }
+    else if c == zero {
+      if a >= zero { FloatInterval::new(down(a, d), inf) }
+      else if b <= zero { FloatInterval::new(neg_inf, up(b, d)) }
+      else { FloatInterval::whole() }
+    }
+    else if d == zero {
+      if a >= zero { FloatInterval::new(neg_inf, up(a, c)) }
+      else if b <= zero { FloatInterval::new(down(b, c), inf) }
+      else { FloatInterval::whole() }
+    }
+    else { FloatInterval::whole() }
+  }
+}
+
+macro_rules! float_scalar_arithmetic_impl
+{
+  ( $( $t:ty ),* ) =>
+  {$(
+    float_scalar_binary_impl!($t;
+      Add, add;
+      Sub, sub;
+      Mul, mul;
+      Div, div);
+  )*}
+}
+
+macro_rules! float_scalar_binary_impl
+{
+  ( $t:ty; $( $op:ident, $m:ident );* ) =>
+  {$(
+    impl $op<$t> for FloatInterval<$t>
+    {
+      type Output = FloatInterval<$t>;
+
+      fn $m(self, other: $t) -> FloatInterval<$t> {
+        self.$m(FloatInterval::singleton(other))
+      }
+    }
+
+    impl $op<FloatInterval<$t>> for $t
+    {
+      type Output = FloatInterval<$t>;
+
+      fn $m(self, other: FloatInterval<$t>) -> FloatInterval<$t> {
+        FloatInterval::singleton(self).$m(other)
+      }
+    }
+  )*}
+}
+
+float_scalar_arithmetic_impl!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ops::lattice::test::*;
+  use ops::set::test::*;
+  use trilean::SKleene::*;
+
+  const INFINITY: f64 = f64::INFINITY;
+  const NEG_INFINITY: f64 = f64::NEG_INFINITY;
+  const NAN: f64 = f64::NAN;
+
+  fn i(lb: f64, ub: f64) -> FloatInterval<f64> {
+    FloatInterval::new(lb, ub)
+  }
+
+  fn empty() -> FloatInterval<f64> {
+    FloatInterval::empty()
+  }
+
+  fn whole() -> FloatInterval<f64> {
+    FloatInterval::whole()
+  }
+
+  #[test]
+  fn constructors_test() {
+    assert_eq!(i(2.0, 1.0), empty());
+    assert_eq!(i(NAN, 1.0), i(NEG_INFINITY, 1.0));
+    assert_eq!(i(0.0, NAN), i(0.0, INFINITY));
+    assert_eq!(FloatInterval::singleton(NAN), whole());
+    assert!(empty().is_empty());
+    assert!(!whole().is_empty());
+    assert!(i(-0.0, 0.0).is_singleton());
+    assert_eq!(i(-0.0, -0.0).lower().to_bits(), 0.0f64.to_bits());
+    assert!(i(1.0, INFINITY).contains(&INFINITY));
+    assert!(!whole().contains(&NAN));
+    assert_eq!(format!("{}", i(-1.5, INFINITY)), "[-1.5..inf]");
+    assert_eq!(format!("{}", empty()), "{}");
+  }
+
+  #[test]
+  fn shrink_test() {
+    let a = i(0.0, 1.0);
+    assert_eq!(a.shrink_left(0.5), i(0.5, 1.0));
+    assert_eq!(a.shrink_left(NAN), a);
+    assert_eq!(a.shrink_right(-1.0), empty());
+    assert_eq!(a.strict_shrink_left(0.5), i(0.5f64.successor().unwrap(), 1.0));
+    assert_eq!(a.strict_shrink_right(1.0).upper(), 1.0 - f64::EPSILON / 2.0);
+    assert_eq!(a.strict_shrink_left(1.0), empty());
+    assert_eq!(whole().strict_shrink_left(INFINITY), empty());
+    assert_eq!(whole().strict_shrink_right(NEG_INFINITY), empty());
+    assert_eq!(whole().strict_shrink_left(f64::MAX), i(INFINITY, INFINITY));
+    assert_eq!(whole().strict_shrink_left(NAN), whole());
+    assert_eq!(FloatInterval::new(0.0f32, 1.0).strict_shrink_left(0.0).lower(), f32::from_bits(1));
+  }
+
+  fn test_data() -> Vec<FloatInterval<f64>> {
+    vec![empty(), i(0.0, 0.0), i(0.0, 1.0), i(0.5, 2.0), i(-1.0, 0.0), i(1.0, INFINITY), i(NEG_INFINITY, -2.0), whole()]
+  }
+
+  #[test]
+  fn set_test() {
+    assert_eq!(i(0.0, 2.0).difference(&i(1.0, 3.0)), i(0.0, 1.0f64.predecessor().unwrap()));
+    assert_eq!(i(0.0, 2.0).difference(&i(0.5, 1.0)), i(0.0, 2.0));
+    assert_eq!(i(0.0, INFINITY).complement(), i(NEG_INFINITY, -f64::from_bits(1)));
+    assert_eq!(whole().complement(), empty());
+    assert_eq!(empty().complement(), whole());
+    assert_eq!(i(0.0, 1.0).symmetric_difference(&i(0.0, 2.0)), i(1.0f64.successor().unwrap(), 2.0));
+    assert!(i(0.0, 1.0).is_proper_subset(&i(0.0, 2.0)));
+    assert!(i(0.0, 1.0).overlap(&i(1.0, 2.0)));
+    assert!(i(0.0, 1.0).is_disjoint(&i(1.5, 2.0)));
+    let elements = vec![NEG_INFINITY, -2.0, -0.5, 0.0, 0.5, 1.0, 3.0, INFINITY, NAN];
+    MaySetTester::new(0, test_data(), elements).test_all();
+  }
+
+  #[test]
+  fn lattice_test() {
+    let data_a =          vec![empty(),    i(0.0, 1.0), i(0.0, 1.0), i(0.0, 1.0), i(1.0, INFINITY)];
+    let data_b =          vec![i(0.0, 1.0), i(0.5, 2.0), i(0.0, 0.5), whole(),    i(NEG_INFINITY, -2.0)];
+    let expected_entail = vec![False,      Unknown,     True,        False,       Unknown];
+    let expected_join =   vec![i(0.0, 1.0), i(0.0, 2.0), i(0.0, 1.0), whole(),    whole()];
+    let expected_meet =   vec![empty(),    i(0.5, 1.0), i(0.0, 0.5), i(0.0, 1.0), empty()];
+    LatticeTester::new(0, data_a, data_b, expected_entail, expected_join, expected_meet).test_all();
+
+    let bounds = [NEG_INFINITY, -2.0, -0.5, 0.0, 1.0, 1.5, INFINITY];
+    RandomLatticeTester::new(1, |rng: &mut Rng| {
+      let (a, b) = (bounds[rng.below(7) as usize], bounds[rng.below(7) as usize]);
+      FloatInterval::new(a.min(b), a.max(b))
+    }).seed(test_seed(1)).test_all();
+
+    let tester = WideningTester::new(2, vec![i(0.0, 1.0), empty()], vec![i(0.5, 3.0), i(-1.0, -1.0)]);
+    tester.test_chain(20, 1, |n| i(0.0, n as f64));
+    tester.test_all();
+    assert_eq!(i(0.0, INFINITY).narrow(i(0.0, 10.0)), i(0.0, 10.0));
+  }
+
+  #[test]
+  fn arithmetics_test() {
+    assert_eq!(i(1.0, 2.0) + i(0.5, 1.0), i(1.5, 3.0));
+    assert_eq!(i(1.0, 2.0) - i(0.5, 1.0), i(0.0, 1.5));
+    assert_eq!(i(-1.0, 2.0) * i(-3.0, 0.5), i(-6.0, 3.0));
+    assert_eq!(i(1.0, 2.0) / i(4.0, 8.0), i(0.125, 0.5));
+    assert_eq!(-i(1.0, INFINITY), i(NEG_INFINITY, -1.0));
+    assert_eq!(i(1.0, 2.0) + empty(), empty());
+    assert_eq!(i(1.0, 2.0) * 2.0, i(2.0, 4.0));
+    assert_eq!(1.0 - i(1.0, 2.0), i(-1.0, 0.0));
+    assert_eq!(i(1.0, 2.0) + NAN, whole());
+
+    // Infinite bounds.
+    assert_eq!(i(1.0, INFINITY) + i(1.0, 1.0), i(2.0, INFINITY));
+    assert_eq!(i(1.0, INFINITY) - i(1.0, INFINITY), whole());
+    assert_eq!(i(0.0, INFINITY) * i(0.0, 0.0), i(0.0, 0.0));
+    assert_eq!(i(2.0, INFINITY) * i(-1.0, -1.0), i(NEG_INFINITY, -2.0));
+    assert_eq!(i(1.0, INFINITY) / i(INFINITY, INFINITY), i(0.0, INFINITY));
+
+    // Division by an interval containing zero.
+    assert_eq!(i(1.0, 2.0) / i(0.0, 0.0), empty());
+    assert_eq!(i(0.0, 0.0) / i(-1.0, 1.0), i(0.0, 0.0));
+    assert_eq!(i(1.0, 2.0) / i(0.0, 4.0), i(0.25, INFINITY));
+    assert_eq!(i(-2.0, -1.0) / i(0.0, 4.0), i(NEG_INFINITY, -0.25));
+    assert_eq!(i(1.0, 2.0) / i(-4.0, 0.0), i(NEG_INFINITY, -0.25));
+    assert_eq!(i(-2.0, -1.0) / i(-4.0, 0.0), i(0.25, INFINITY));
+    assert_eq!(i(-2.0, 1.0) / i(0.0, 4.0), whole());
+    assert_eq!(i(1.0, 2.0) / i(-1.0, 1.0), whole());
+  }
+
+  #[test]
+  fn rounding_test() {
+    // Exact operations are not widened.
+    assert_eq!(i(0.5, 0.5) + i(0.25, 0.25), i(0.75, 0.75));
+    assert_eq!(i(3.0, 3.0) * i(0.5, 0.5), i(1.5, 1.5));
+    assert_eq!(i(1.0, 1.0) / i(4.0, 4.0), i(0.25, 0.25));
+
+    let sum = i(0.1, 0.1) + i(0.2, 0.2);
+    assert_eq!(sum, i(0.3, 0.30000000000000004));
+    let third = i(1.0, 1.0) / i(3.0, 3.0);
+    assert_eq!(third.upper(), (1.0f64 / 3.0).successor().unwrap());
+    assert_eq!(third.lower(), 1.0 / 3.0);
+
+    // Overflow and underflow.
+    assert_eq!(i(f64::MAX, f64::MAX) + i(f64::MAX, f64::MAX), i(f64::MAX, INFINITY));
+    assert_eq!(i(f64::MAX, f64::MAX) * i(-2.0, -2.0), i(NEG_INFINITY, f64::MIN));
+    let tiny = f64::from_bits(1);
+    assert_eq!(i(tiny, tiny) * i(0.5, 0.5), i(-tiny, tiny));
+    assert_eq!(i(tiny, tiny) / i(-2.0, -2.0), i(-tiny, tiny));
+  }
+
+  /// A value of `x`: one of the bounds or a random value in between.
+  fn sample(rng: &mut Rng, x: &FloatInterval<f32>) -> f32 {
+    match rng.below(3) {
+      0 => x.lower(),
+      1 => x.upper(),
+      _ => {
+        let t = (rng.below(1001) as f32) / 1000.0;
+        let v = x.lower() + t * (x.upper() - x.lower());
+        if x.contains(&v) { v } else { x.lower() }
+      }
+    }
+  }
+
+  fn arbitrary_f32(rng: &mut Rng) -> FloatInterval<f32> {
+    let bound = |rng: &mut Rng| {
+      let mantissa = (rng.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+      let exponent = rng.below(21) as i32 - 10;
+      let sign = if rng.next_bool() { -1.0 } else { 1.0 };
+      sign * (1.0 + mantissa) * 2f32.powi(exponent)
+    };
+    let (a, b) = (bound(rng), bound(rng));
+    FloatInterval::new(a.min(b), a.max(b))
+  }
+
+  /// The operations on `f32` are checked with the same operations on `f64`, which are exact for the addition, subtraction and multiplication of these values, and rounded to the nearest for the division.
+  /// Since the rounding to the nearest is monotone, the `f64` result stays between the `f32` bounds of the exact result.
+  #[test]
+  fn soundness_test() {
+    let seed = test_seed(0);
+    let mut rng = Rng::new(seed);
+    for _ in 0..2000 {
+      let (x, y) = (arbitrary_f32(&mut rng), arbitrary_f32(&mut rng));
+      let (a, b) = (sample(&mut rng, &x), sample(&mut rng, &y));
+      let (a64, b64) = (a as f64, b as f64);
+      let results: [(&str, FloatInterval<f32>, f64); 4] =
+        [("+", x + y, a64 + b64), ("-", x - y, a64 - b64), ("*", x * y, a64 * b64), ("/", x / y, a64 / b64)];
+      for &(name, result, exact) in &results {
+        assert!(exact.is_nan() || (result.lower() as f64 <= exact && exact <= result.upper() as f64),
+          "[seed {}] {} {} {} = {} does not contain {} {} {} = {}", seed, x, name, y, result, a, name, b, exact);
+      }
+    }
+  }
+}
@@ -0,0 +1,532 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Sets of integers represented by a sorted list of disjoint intervals.
+//!
+//! Contrarily to `Interval`, which only keeps the hull, an interval set represents the holes of a domain exactly, and it does not pay for the values in between as a `BitSet` does.
+//! The intervals are sorted, disjoint and non-adjacent (at least one value lies between two intervals), thus the representation is unique and the equality is the equality of the lists.
+//! The complement is taken within `Interval::whole()`, and the lattice is the powerset lattice where the join is the union and the meet is the intersection.
+//!
+//! ```rust
+//! use gcollections::ops::*;
+//! use gcollections::interval::{Interval, IntervalSet};
+//!
+//! let mut a = IntervalSet::new(1, 9);
+//! assert!(a.remove(&5));
+//! assert_eq!(a.intervals(), &[Interval::new(1, 4), Interval::new(6, 9)]);
+//! assert_eq!(a.size(), 8u32);
+//! let b = a.union(&IntervalSet::new(10, 12));
+//! assert_eq!(b.intervals(), &[Interval::new(1, 4), Interval::new(6, 12)]);
+//! assert!(!b.contains(&5));
+//! ```
+
+use kind::*;
+use ops::*;
+use interval::{Interval, Width};
+use wrappers::powerset::{subset_entailment, display_set};
+use trilean::SKleene;
+use num_traits::Zero;
+use num_integer::Integer;
+use std::cmp::{min, max, Ordering};
+use std::fmt::{Formatter, Display, Error};
+#[cfg(test)]
+use ops::lattice::test::{Arbitrary, Rng};
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct IntervalSet<B>
+{
+  intervals: Vec<Interval<B>>
+}
+
+impl<B> IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  /// The set `[lb..ub]`, it is empty if `lb > ub`.
+  pub fn new(lb: B, ub: B) -> IntervalSet<B> {
+    IntervalSet::from_intervals(vec![Interval::new(lb, ub)])
+  }
+
+  /// The union of `intervals`, which can be empty, unsorted or overlapping.
+  pub fn from_intervals(mut intervals: Vec<Interval<B>>) -> IntervalSet<B> {
+    intervals.retain(|i| !i.is_empty());
+    intervals.sort_by_key(|i| i.lb);
+    let mut set = IntervalSet::empty();
+    for i in intervals {
+      set.push(i);
+    }
+    set
+  }
+
+  /// The set containing every value between `Width::min_bound()` and `Width::max_bound()`.
+  pub fn whole() -> IntervalSet<B> {
+    IntervalSet::new(B::min_bound(), B::max_bound())
+  }
+
+  /// The intervals of the set in increasing order.
+  pub fn intervals(&self) -> &[Interval<B>] {
+    &self.intervals
+  }
+
+  /// Appends a non-empty interval starting after the lower bound of the last interval, they are merged if they overlap or are adjacent.
+  fn push(&mut self, i: Interval<B>) {
+    if let Some(last) = self.intervals.last_mut() {
+      // `i.lb - 1` is only computed if `i.lb > last.ub`, thus it does not underflow.
+      if i.lb <= last.ub || i.lb - B::one() <= last.ub {
+        last.ub = max(last.ub, i.ub);
+        return;
+      }
+    }
+    self.intervals.push(i);
+  }
+
+  /// Index of the interval containing `value`, or the index where it would be inserted.
+  fn search(&self, value: &B) -> Result<usize, usize> {
+    self.intervals.binary_search_by(|i| {
+      if i.ub < *value { Ordering::Less }
+      else if i.lb > *value { Ordering::Greater }
+      else { Ordering::Equal }
+    })
+  }
+}
+
+impl<B> Collection for IntervalSet<B> {
+  type Item = B;
+}
+
+impl<B> IntervalKind for IntervalSet<B> {}
+
+impl<B> Bounded for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn lower(&self) -> B {
+    debug_assert!(!self.intervals.is_empty(), "Cannot access the lower bound of an empty interval set.");
+    self.intervals[0].lb
+  }
+
+  fn upper(&self) -> B {
+    debug_assert!(!self.intervals.is_empty(), "Cannot access the upper bound of an empty interval set.");
+    self.intervals[self.intervals.len() - 1].ub
+  }
+}
+
+impl<B> ShrinkLeft for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn shrink_left(&self, lb: B) -> IntervalSet<B> {
+    let mut intervals: Vec<_> = self.intervals.iter().cloned()
+      .skip_while(|i| i.ub < lb)
+      .collect();
+    if let Some(first) = intervals.first_mut() {
+      first.lb = max(first.lb, lb);
+    }
+    IntervalSet {
+      intervals: intervals
+    }
+  }
+}
+
+impl<B> ShrinkRight for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn shrink_right(&self, ub: B) -> IntervalSet<B> {
+    let mut intervals: Vec<_> = self.intervals.iter().cloned()
+      .take_while(|i| i.lb <= ub)
+      .collect();
+    if let Some(last) = intervals.last_mut() {
+      last.ub = min(last.ub, ub);
+    }
+    IntervalSet {
+      intervals: intervals
+    }
+  }
+}
+
+impl<B> Cardinality for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  type Size = <B as Width>::Output;
+
+  fn size(&self) -> <B as Width>::Output {
+    self.intervals.iter().fold(<B as Width>::Output::zero(), |size, i| size + i.size())
+  }
+}
+
+impl<B> Empty for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn empty() -> IntervalSet<B> {
+    IntervalSet {
+      intervals: vec![]
+    }
+  }
+}
+
+impl<B> Singleton for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn singleton(value: B) -> IntervalSet<B> {
+    IntervalSet::new(value, value)
+  }
+}
+
+impl<B> Contains for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn contains(&self, value: &B) -> bool {
+    self.search(value).is_ok()
+  }
+}
+
+/// Punches a hole in the interval containing `value`, which is split in two if `value` is not one of its bounds.
+impl<B> Remove for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn remove(&mut self, value: &B) -> bool {
+    let idx = match self.search(value) {
+      Ok(idx) => idx,
+      Err(_) => return false
+    };
+    let i = self.intervals[idx];
+    if i.lb == i.ub {
+      self.intervals.remove(idx);
+    }
+    else if i.lb == *value {
+      self.intervals[idx].lb = *value + B::one();
+    }
+    else if i.ub == *value {
+      self.intervals[idx].ub = *value - B::one();
+    }
+    else {
+      self.intervals[idx].ub = *value - B::one();
+      self.intervals.insert(idx + 1, Interval::new(*value + B::one(), i.ub));
+    }
+    true
+  }
+}
+
+impl<B> Intersection for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  type Output = IntervalSet<B>;
+
+  fn intersection(&self, other: &IntervalSet<B>) -> IntervalSet<B> {
+    let (a, b) = (&self.intervals, &other.intervals);
+    let mut result = IntervalSet::empty();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+      let common = a[i].intersection(&b[j]);
+      if !common.is_empty() {
+        result.push(common);
+      }
+      if a[i].ub < b[j].ub { i += 1; } else { j += 1; }
+    }
+    result
+  }
+}
+
+impl<B> Union for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  type Output = IntervalSet<B>;
+
+  fn union(&self, other: &IntervalSet<B>) -> IntervalSet<B> {
+    let (a, b) = (&self.intervals, &other.intervals);
+    let mut result = IntervalSet::empty();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+      if j == b.len() || (i < a.len() && a[i].lb <= b[j].lb) {
+        result.push(a[i]);
+        i += 1;
+      }
+      else {
+        result.push(b[j]);
+        j += 1;
+      }
+    }
+    result
+  }
+}
+
+impl<B> Difference for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  type Output = IntervalSet<B>;
+
+  fn difference(&self, other: &IntervalSet<B>) -> IntervalSet<B> {
+    self.intersection(&other.complement())
+  }
+}
+
+impl<B> SymmetricDifference for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  type Output = IntervalSet<B>;
+
+  fn symmetric_difference(&self, other: &IntervalSet<B>) -> IntervalSet<B> {
+    self.difference(other).union(&other.difference(self))
+  }
+}
+
+/// The complement in `IntervalSet::whole()`, it is exact.
+impl<B> Complement for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn complement(&self) -> IntervalSet<B> {
+    let mut result = IntervalSet::empty();
+    // Smallest value not yet covered by `self` or `result`.
+    let mut lb = B::min_bound();
+    for i in &self.intervals {
+      if lb < i.lb {
+        result.intervals.push(Interval::new(lb, i.lb - B::one()));
+      }
+      if i.ub == B::max_bound() {
+        return result;
+      }
+      lb = i.ub + B::one();
+    }
+    result.intervals.push(Interval::new(lb, B::max_bound()));
+    result
+  }
+}
+
+impl<B> Disjoint for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn is_disjoint(&self, other: &IntervalSet<B>) -> bool {
+    let (a, b) = (&self.intervals, &other.intervals);
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+      if a[i].overlap(&b[j]) {
+        return false;
+      }
+      if a[i].ub < b[j].ub { i += 1; } else { j += 1; }
+    }
+    true
+  }
+}
+
+impl<B> Overlap for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn overlap(&self, other: &IntervalSet<B>) -> bool {
+    !self.is_disjoint(other)
+  }
+}
+
+/// Every interval of `self` must be included in a single interval of `other` since the intervals of `other` are not adjacent.
+impl<B> Subset for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn is_subset(&self, other: &IntervalSet<B>) -> bool {
+    let b = &other.intervals;
+    let mut j = 0;
+    for i in &self.intervals {
+      while j < b.len() && b[j].ub < i.lb {
+        j += 1;
+      }
+      if j == b.len() || !i.is_subset(&b[j]) {
+        return false;
+      }
+    }
+    true
+  }
+}
+
+impl<B> ProperSubset for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn is_proper_subset(&self, other: &IntervalSet<B>) -> bool {
+    self.is_subset(other) && self != other
+  }
+}
+
+impl<B> Display for IntervalSet<B> where
+ B: Display + Width + Integer + Copy
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    display_set(self.intervals.iter(), formatter)
+  }
+}
+
+impl<B> Join for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn join(self, other: IntervalSet<B>) -> IntervalSet<B> {
+    self.union(&other)
+  }
+}
+
+impl<B> Meet for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn meet(self, other: IntervalSet<B>) -> IntervalSet<B> {
+    self.intersection(&other)
+  }
+}
+
+impl<B> Entailment for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn entail(&self, other: &IntervalSet<B>) -> SKleene {
+    subset_entailment(self, other)
+  }
+}
+
+impl<B> Top for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn top() -> IntervalSet<B> {
+    IntervalSet::whole()
+  }
+}
+
+impl<B> Bot for IntervalSet<B> where
+ B: Width + Integer + Copy
+{
+  fn bot() -> IntervalSet<B> {
+    IntervalSet::empty()
+  }
+}
+
+/// Unions of up to three arbitrary intervals.
+#[cfg(test)]
+impl<B> Arbitrary for IntervalSet<B> where
+ B: Arbitrary + Width + Integer + Copy
+{
+  fn arbitrary(rng: &mut Rng) -> IntervalSet<B> {
+    let n = rng.below(4);
+    IntervalSet::from_intervals((0..n).map(|_| Interval::arbitrary(rng)).collect())
+  }
+
+  /// Shrinks by dropping one of the intervals.
+  fn shrink(&self) -> Vec<IntervalSet<B>> {
+    (0..self.intervals().len()).map(|k| {
+      let mut intervals = self.intervals().to_vec();
+      intervals.remove(k);
+      IntervalSet::from_intervals(intervals)
+    }).collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ops::lattice::test::*;
+  use ops::set::test::*;
+  use wrappers::btree_set::BTreeSet;
+  use trilean::SKleene::*;
+
+  fn s(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+    IntervalSet::from_intervals(intervals.iter().map(|&(lb, ub)| Interval::new(lb, ub)).collect())
+  }
+
+  fn empty() -> IntervalSet<i32> {
+    IntervalSet::empty()
+  }
+
+  fn test_data() -> Vec<IntervalSet<i32>> {
+    vec![empty(), s(&[(0, 0)]), s(&[(1, 2)]), s(&[(0, 3)]), s(&[(-3, 0), (2, 5)]),
+      s(&[(0, 0), (2, 2), (4, 4)]), s(&[(-5, -2), (1, 1), (6, 8)]), s(&[(-5, 8)])]
+  }
+
+  fn to_set(a: &IntervalSet<i32>) -> BTreeSet<i32> {
+    BTreeSet::wrap(a.intervals().iter().flat_map(|i| i.lower()..i.upper() + 1).collect())
+  }
+
+  #[test]
+  fn constructors_test() {
+    assert_eq!(s(&[(5, 6), (1, 3), (4, 4), (10, 9)]), s(&[(1, 6)]));
+    assert_eq!(s(&[(1, 3), (5, 6), (2, 4)]).intervals(), &[Interval::new(1, 6)]);
+    assert_eq!(s(&[(7, 9), (1, 3)]).intervals(), &[Interval::new(1, 3), Interval::new(7, 9)]);
+    assert_eq!(s(&[(3, 2)]), empty());
+    assert!(empty().is_empty());
+    assert_eq!(empty().size(), 0u32);
+    assert_eq!(s(&[(-2, 2), (5, 6)]).size(), 7u32);
+    assert!(IntervalSet::singleton(4).is_singleton());
+    assert_eq!(IntervalSet::<u8>::whole().size(), 255u8);
+    assert_eq!(s(&[(1, 2), (5, 6)]).lower(), 1);
+    assert_eq!(s(&[(1, 2), (5, 6)]).upper(), 6);
+    assert_eq!(format!("{}", s(&[(1, 2), (5, 5)])), "{[1..2], [5..5]}");
+    assert_eq!(format!("{}", empty()), "{}");
+  }
+
+  #[test]
+  fn shrink_test() {
+    let a = s(&[(0, 2), (5, 7), (10, 12)]);
+    assert_eq!(a.shrink_left(1), s(&[(1, 2), (5, 7), (10, 12)]));
+    assert_eq!(a.shrink_left(3), s(&[(5, 7), (10, 12)]));
+    assert_eq!(a.shrink_left(13), empty());
+    assert_eq!(a.shrink_right(6), s(&[(0, 2), (5, 6)]));
+    assert_eq!(a.shrink_right(9), s(&[(0, 2), (5, 7)]));
+    assert_eq!(a.shrink_right(-1), empty());
+    assert_eq!(a.strict_shrink_left(7), s(&[(10, 12)]));
+    assert_eq!(a.strict_shrink_right(5), s(&[(0, 2)]));
+    assert_eq!(empty().shrink_left(0), empty());
+    assert_eq!(IntervalSet::<u8>::whole().strict_shrink_right(0), IntervalSet::empty());
+  }
+
+  #[test]
+  fn remove_test() {
+    let mut a = s(&[(0, 5), (8, 8)]);
+    assert!(a.remove(&3));
+    assert_eq!(a, s(&[(0, 2), (4, 5), (8, 8)]));
+    assert!(!a.remove(&3));
+    assert!(!a.remove(&7));
+    assert!(a.remove(&0));
+    assert!(a.remove(&5));
+    assert!(a.remove(&8));
+    assert_eq!(a, s(&[(1, 2), (4, 4)]));
+    assert_eq!(a.size(), 3u32);
+    let mut b = IntervalSet::<u8>::whole();
+    assert!(b.remove(&0));
+    assert!(b.remove(&254));
+    assert_eq!(b, IntervalSet::new(1, 253));
+    let mut c = IntervalSet::singleton(1);
+    assert!(c.remove(&1));
+    assert_eq!(c, IntervalSet::empty());
+  }
+
+  #[test]
+  fn set_test() {
+    for a in test_data() {
+      for x in -7..11 {
+        assert_eq!(a.contains(&x), to_set(&a).contains(&x), "{} contains {}", a, x);
+        assert_ne!(a.contains(&x), a.complement().contains(&x), "{} in {} or in its complement", x, a);
+      }
+      assert_eq!(a.complement().complement(), a);
+      for b in test_data() {
+        let (sa, sb) = (to_set(&a), to_set(&b));
+        assert_eq!(a.is_disjoint(&b), sa.is_disjoint(&sb), "{} disjoint {}", a, b);
+        assert_eq!(a.overlap(&b), sa.overlap(&sb), "{} overlap {}", a, b);
+        assert_eq!(a.is_subset(&b), sa.is_subset(&sb), "{} subset {}", a, b);
+        assert_eq!(a.is_proper_subset(&b), sa.is_proper_subset(&sb), "{} proper subset {}", a, b);
+        assert_eq!(to_set(&a.intersection(&b)), sa.intersection(&sb), "{} intersection {}", a, b);
+        assert_eq!(to_set(&a.union(&b)), sa.union(&sb), "{} union {}", a, b);
+        assert_eq!(to_set(&a.difference(&b)), sa.difference(&sb), "{} difference {}", a, b);
+        assert_eq!(to_set(&a.symmetric_difference(&b)), sa.symmetric_difference(&sb),
+          "{} symmetric difference {}", a, b);
+      }
+    }
+    assert_eq!(s(&[(0, 10)]).complement(), s(&[(-i32::MAX, -1), (11, i32::MAX)]));
+    assert_eq!(IntervalSet::<i32>::whole().complement(), empty());
+    assert_eq!(empty().complement(), IntervalSet::whole());
+    MaySetTester::new(0, test_data(), (-7..11).collect()).test_all();
+  }
+
+  #[test]
+  fn lattice_test() {
+    let data_a =          vec![empty(),       s(&[(1, 2)]), s(&[(0, 5)]),         s(&[(1, 2)]),         s(&[(0, 3)]), s(&[(0, 0)])];
+    let data_b =          vec![s(&[(1, 2)]),  empty(),      s(&[(1, 2), (4, 4)]), s(&[(4, 6)]),         s(&[(2, 5)]), IntervalSet::whole()];
+    let expected_entail = vec![False,         True,         True,                 Unknown,              Unknown,      False];
+    let expected_join =   vec![s(&[(1, 2)]),  s(&[(1, 2)]), s(&[(0, 5)]),         s(&[(1, 2), (4, 6)]), s(&[(0, 5)]), IntervalSet::whole()];
+    let expected_meet =   vec![empty(),       empty(),      s(&[(1, 2), (4, 4)]), empty(),              s(&[(2, 3)]), s(&[(0, 0)])];
+    LatticeTester::new(0, data_a, data_b, expected_entail, expected_join, expected_meet).test_all();
+    RandomLatticeTester::<IntervalSet<i8>, _>::arbitrary(1).seed(test_seed(1)).test_all();
+    RandomLatticeTester::<IntervalSet<u16>, _>::arbitrary(2).seed(test_seed(2)).test_all();
+  }
+}
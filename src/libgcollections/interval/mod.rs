@@ -0,0 +1,584 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Closed intervals `[lower..upper]` over integer bounds.
+//!
+//! An interval is a set of integers, it implements the operations of `ops::set`.
+//! The operations whose result is not an interval (such as the union of two disjoint intervals) return the smallest interval containing the result, called the hull.
+//! Intervals are ordered by inclusion and form a lattice where the join is the hull of the union and the meet is the intersection.
+//!
+//! The bounds are restricted by `Width` so that the size of every interval fits in an unsigned integer of the same width.
+//! For example, `Interval<i8>` ranges over `[-127..127]` and `Interval<u8>` over `[0..254]`.
+//!
+//! ```rust
+//! use gcollections::ops::*;
+//! use gcollections::interval::Interval;
+//!
+//! let a = Interval::new(1, 5);
+//! let b = Interval::new(4, 9);
+//! assert_eq!(a.intersection(&b), Interval::new(4, 5));
+//! assert_eq!(a.union(&Interval::new(8, 9)), Interval::new(1, 9));
+//! assert_eq!(a.strict_shrink_left(4), Interval::singleton(5));
+//! assert!(a.strict_shrink_right(1).is_empty());
+//! ```
+
+use kind::*;
+use ops::*;
+use wrappers::powerset::subset_entailment;
+use trilean::SKleene;
+use num_traits::{Unsigned, Zero};
+use num_traits::Bounded as NumBounded;
+use num_integer::Integer;
+use std::cmp::{min, max};
+use std::fmt::{Formatter, Display, Error};
+#[cfg(test)]
+use ops::lattice::test::{Arbitrary, Rng};
+
+mod arithmetic;
+mod float;
+mod interval_set;
+
+pub use self::float::FloatInterval;
+pub use self::interval_set::IntervalSet;
+
+/// Bounds of an interval: `min_bound()` and `max_bound()` are chosen such that the size of `[min_bound()..max_bound()]` fits in `Output`.
+pub trait Width: Ord + Clone + NumBounded
+{
+  type Output: Unsigned + Integer + Clone;
+
+  fn max_bound() -> Self;
+  fn min_bound() -> Self;
+
+  /// Size of the interval `[lower..upper]`, it requires `lower <= upper`.
+  fn width(lower: &Self, upper: &Self) -> Self::Output;
+}
+
+macro_rules! signed_width_impl
+{
+  ( $( $t:ty, $u:ty ),* ) =>
+  {$(
+    /// The bounds are symmetric, thus the negation of a bound is always a bound.
+    impl Width for $t
+    {
+      type Output = $u;
+
+      fn max_bound() -> $t {
+        <$t>::MAX
+      }
+
+      fn min_bound() -> $t {
+        -<$t>::MAX
+      }
+
+      fn width(lower: &$t, upper: &$t) -> $u {
+        (upper.wrapping_sub(*lower) as $u) + 1
+      }
+    }
+  )*}
+}
+
+macro_rules! unsigned_width_impl
+{
+  ( $( $t:ty ),* ) =>
+  {$(
+    impl Width for $t
+    {
+      type Output = $t;
+
+      fn max_bound() -> $t {
+        <$t>::MAX - 1
+      }
+
+      fn min_bound() -> $t {
+        0
+      }
+
+      fn width(lower: &$t, upper: &$t) -> $t {
+        upper - lower + 1
+      }
+    }
+  )*}
+}
+
+signed_width_impl!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+unsigned_width_impl!(u8, u16, u32, u64, usize);
+
+/// The empty interval is represented by `[1..0]`, every interval with `lower > upper` is normalized to it, thus the equality is the equality of the bounds.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Interval<B>
+{
+  lb: B,
+  ub: B
+}
+
+impl<B> Interval<B> where
+ B: Width + Integer + Copy
+{
+  /// The interval `[lb..ub]`, it is empty if `lb > ub`.
+  /// Panics if the interval is not empty and one of its bounds is outside of `[Width::min_bound()..Width::max_bound()]`, for example `Interval::new(0u8, 255)` (see `checked_new`).
+  pub fn new(lb: B, ub: B) -> Interval<B> {
+    assert!(lb > ub || (lb >= B::min_bound() && ub <= B::max_bound()),
+      "The bounds of a non-empty interval must be between `Width::min_bound()` and `Width::max_bound()`.");
+    if lb > ub {
+      Interval::empty()
+    }
+    else {
+      Interval {
+        lb: lb,
+        ub: ub
+      }
+    }
+  }
+
+  /// The interval `[lb..ub]`, or `None` if one of the bounds of a non-empty interval is outside of `Interval::whole()`.
+  pub fn checked_new(lb: B, ub: B) -> Option<Interval<B>> {
+    if lb <= ub && (lb < B::min_bound() || ub > B::max_bound()) { None }
+    else { Some(Interval::new(lb, ub)) }
+  }
+
+  /// The interval containing every value between `Width::min_bound()` and `Width::max_bound()`.
+  pub fn whole() -> Interval<B> {
+    Interval::new(B::min_bound(), B::max_bound())
+  }
+
+  fn is_empty_interval(&self) -> bool {
+    self.lb > self.ub
+  }
+}
+
+impl<B> Collection for Interval<B> {
+  type Item = B;
+}
+
+impl<B> IntervalKind for Interval<B> {}
+
+impl<B> Bounded for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn lower(&self) -> B {
+    debug_assert!(!self.is_empty_interval(), "Cannot access the lower bound of an empty interval.");
+    self.lb
+  }
+
+  fn upper(&self) -> B {
+    debug_assert!(!self.is_empty_interval(), "Cannot access the upper bound of an empty interval.");
+    self.ub
+  }
+}
+
+impl<B> ShrinkLeft for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn shrink_left(&self, lb: B) -> Interval<B> {
+    if self.is_empty_interval() { *self }
+    else { Interval::new(max(self.lb, lb), self.ub) }
+  }
+}
+
+impl<B> ShrinkRight for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn shrink_right(&self, ub: B) -> Interval<B> {
+    if self.is_empty_interval() { *self }
+    else { Interval::new(self.lb, min(self.ub, ub)) }
+  }
+}
+
+impl<B> Cardinality for Interval<B> where
+ B: Width + Integer + Copy
+{
+  type Size = <B as Width>::Output;
+
+  fn size(&self) -> <B as Width>::Output {
+    if self.is_empty_interval() { <B as Width>::Output::zero() }
+    else { B::width(&self.lb, &self.ub) }
+  }
+}
+
+impl<B> Empty for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn empty() -> Interval<B> {
+    Interval {
+      lb: B::one(),
+      ub: B::zero()
+    }
+  }
+}
+
+impl<B> Singleton for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn singleton(value: B) -> Interval<B> {
+    Interval::new(value, value)
+  }
+}
+
+impl<B> Contains for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn contains(&self, value: &B) -> bool {
+    self.lb <= *value && *value <= self.ub
+  }
+}
+
+impl<B> Intersection for Interval<B> where
+ B: Width + Integer + Copy
+{
+  type Output = Interval<B>;
+
+  fn intersection(&self, other: &Interval<B>) -> Interval<B> {
+    Interval::new(max(self.lb, other.lb), min(self.ub, other.ub))
+  }
+}
+
+/// The hull of the union.
+impl<B> Union for Interval<B> where
+ B: Width + Integer + Copy
+{
+  type Output = Interval<B>;
+
+  fn union(&self, other: &Interval<B>) -> Interval<B> {
+    if self.is_empty_interval() { *other }
+    else if other.is_empty_interval() { *self }
+    else { Interval::new(min(self.lb, other.lb), max(self.ub, other.ub)) }
+  }
+}
+
+/// The hull of the difference: it is exact unless `other` is strictly inside `self`, in which case the result is `self`.
+impl<B> Difference for Interval<B> where
+ B: Width + Integer + Copy
+{
+  type Output = Interval<B>;
+
+  fn difference(&self, other: &Interval<B>) -> Interval<B> {
+    if self.is_disjoint(other) { *self }
+    else if other.lb <= self.lb { self.strict_shrink_left(other.ub) }
+    else if other.ub >= self.ub { self.strict_shrink_right(other.lb) }
+    else { *self }
+  }
+}
+
+/// The hull of the symmetric difference.
+impl<B> SymmetricDifference for Interval<B> where
+ B: Width + Integer + Copy
+{
+  type Output = Interval<B>;
+
+  fn symmetric_difference(&self, other: &Interval<B>) -> Interval<B> {
+    self.difference(other).union(&other.difference(self))
+  }
+}
+
+/// The hull of the complement in `Interval::whole()`.
+impl<B> Complement for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn complement(&self) -> Interval<B> {
+    Interval::whole().difference(self)
+  }
+}
+
+impl<B> Disjoint for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn is_disjoint(&self, other: &Interval<B>) -> bool {
+    self.is_empty_interval() || other.is_empty_interval() ||
+    self.ub < other.lb || other.ub < self.lb
+  }
+}
+
+impl<B> Overlap for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn overlap(&self, other: &Interval<B>) -> bool {
+    !self.is_disjoint(other)
+  }
+}
+
+impl<B> Subset for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn is_subset(&self, other: &Interval<B>) -> bool {
+    self.is_empty_interval() ||
+    (other.lb <= self.lb && self.ub <= other.ub)
+  }
+}
+
+impl<B> ProperSubset for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn is_proper_subset(&self, other: &Interval<B>) -> bool {
+    self.is_subset(other) && self != other
+  }
+}
+
+impl<B> Display for Interval<B> where
+ B: Display + Width + Integer + Copy
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    if self.is_empty_interval() {
+      formatter.write_str("{}")
+    }
+    else {
+      write!(formatter, "[{}..{}]", self.lb, self.ub)
+    }
+  }
+}
+
+/// Inclusion lattice: the join is the hull of the union and the meet is the intersection.
+impl<B> Join for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn join(self, other: Interval<B>) -> Interval<B> {
+    self.union(&other)
+  }
+}
+
+impl<B> Meet for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn meet(self, other: Interval<B>) -> Interval<B> {
+    self.intersection(&other)
+  }
+}
+
+impl<B> Entailment for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn entail(&self, other: &Interval<B>) -> SKleene {
+    subset_entailment(self, other)
+  }
+}
+
+impl<B> Top for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn top() -> Interval<B> {
+    Interval::whole()
+  }
+}
+
+impl<B> Bot for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn bot() -> Interval<B> {
+    Interval::empty()
+  }
+}
+
+/// Standard interval widening: a bound which is not stable jumps to the corresponding bound of `Interval::whole()`.
+impl<B> Widen for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn widen(self, other: Interval<B>) -> Interval<B> {
+    if self.is_empty_interval() { other }
+    else if other.is_empty_interval() { self }
+    else {
+      let lb = if other.lb < self.lb { B::min_bound() } else { self.lb };
+      let ub = if other.ub > self.ub { B::max_bound() } else { self.ub };
+      Interval::new(lb, ub)
+    }
+  }
+}
+
+/// Standard interval narrowing: only the bounds of `Interval::whole()` (those introduced by the widening) are refined.
+impl<B> Narrow for Interval<B> where
+ B: Width + Integer + Copy
+{
+  fn narrow(self, other: Interval<B>) -> Interval<B> {
+    if self.is_empty_interval() || other.is_empty_interval() { Interval::empty() }
+    else {
+      let lb = if self.lb == B::min_bound() { other.lb } else { self.lb };
+      let ub = if self.ub == B::max_bound() { other.ub } else { self.ub };
+      Interval::new(lb, ub)
+    }
+  }
+}
+
+#[cfg(test)]
+impl<B> Arbitrary for Interval<B> where
+ B: Arbitrary + Width + Integer + Copy
+{
+  fn arbitrary(rng: &mut Rng) -> Interval<B> {
+    if rng.below(8) == 0 { Interval::empty() }
+    else {
+      let clamp = |x: B| min(max(x, B::min_bound()), B::max_bound());
+      let (a, b) = (clamp(B::arbitrary(rng)), clamp(B::arbitrary(rng)));
+      Interval::new(a.min(b), a.max(b))
+    }
+  }
+
+  /// Shrinks to the empty interval and to the singletons of the bounds.
+  fn shrink(&self) -> Vec<Interval<B>> {
+    if self.is_empty() { vec![] }
+    else {
+      let mut candidates = vec![Interval::empty()];
+      for x in [self.lower(), self.upper()] {
+        let singleton = Interval::singleton(x);
+        if singleton != *self && !candidates.contains(&singleton) {
+          candidates.push(singleton);
+        }
+      }
+      candidates
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ops::lattice::test::*;
+  use ops::set::test::*;
+  use wrappers::btree_set::BTreeSet;
+  use trilean::SKleene::*;
+
+  fn i(lb: i32, ub: i32) -> Interval<i32> {
+    Interval::new(lb, ub)
+  }
+
+  fn empty() -> Interval<i32> {
+    Interval::empty()
+  }
+
+  fn test_data() -> Vec<Interval<i32>> {
+    vec![empty(), i(0, 0), i(1, 1), i(0, 3), i(1, 2), i(2, 5), i(-3, 0), i(4, 8), i(-5, 5)]
+  }
+
+  fn to_set(a: &Interval<i32>) -> BTreeSet<i32> {
+    if a.is_empty() { BTreeSet::empty() }
+    else { BTreeSet::wrap((a.lower()..a.upper() + 1).collect()) }
+  }
+
+  /// Smallest interval containing the values of `set`.
+  fn hull(set: BTreeSet<i32>) -> Interval<i32> {
+    match (set.iter().next(), set.iter().next_back()) {
+      (Some(&lb), Some(&ub)) => i(lb, ub),
+      _ => empty()
+    }
+  }
+
+  #[test]
+  fn constructors_test() {
+    assert_eq!(i(3, 2), empty());
+    assert_eq!(i(i32::MAX, 0), empty());
+    assert!(empty().is_empty());
+    assert_eq!(empty().size(), 0u32);
+    assert_eq!(i(-2, 2).size(), 5u32);
+    assert!(Interval::singleton(4).is_singleton());
+    assert_eq!(Interval::singleton(4), i(4, 4));
+    assert_eq!(Interval::<i8>::whole().size(), 255u8);
+    assert_eq!(Interval::<u8>::whole().size(), 255u8);
+    assert_eq!(Interval::<i64>::whole(), Interval::new(-i64::MAX, i64::MAX));
+    assert_eq!(i(1, 5).lower(), 1);
+    assert_eq!(i(1, 5).upper(), 5);
+    assert_eq!(format!("{}", i(-1, 5)), "[-1..5]");
+    assert_eq!(format!("{}", empty()), "{}");
+  }
+
+  #[test]
+  fn extreme_bounds_test() {
+    assert_eq!(Interval::new(0u8, 254).size(), 255u8);
+    assert_eq!(Interval::new(-i8::MAX, 0).size(), 128u8);
+    assert_eq!(Interval::new(-i64::MAX, i64::MAX), Interval::whole());
+    assert_eq!(Interval::new(255u8, 0), Interval::empty());
+    assert_eq!(Interval::new(0i8, i8::MIN), Interval::empty());
+    assert_eq!(Interval::checked_new(0u8, 255), None);
+    assert_eq!(Interval::checked_new(i8::MIN, 0), None);
+    assert_eq!(Interval::checked_new(0u8, 254), Some(Interval::whole()));
+    assert_eq!(Interval::checked_new(255u8, 0), Some(Interval::empty()));
+    assert_eq!(Interval::checked_new(i16::MIN, i16::MIN), None);
+  }
+
+  #[test]
+  #[should_panic(expected = "The bounds of a non-empty interval")]
+  fn u8_max_singleton_test() {
+    Interval::<u8>::singleton(255);
+  }
+
+  #[test]
+  #[should_panic(expected = "The bounds of a non-empty interval")]
+  fn i8_min_singleton_test() {
+    Interval::<i8>::singleton(i8::MIN);
+  }
+
+  #[test]
+  #[should_panic(expected = "The bounds of a non-empty interval")]
+  fn out_of_range_new_test() {
+    Interval::new(i64::MIN, i64::MAX);
+  }
+
+  #[test]
+  fn shrink_test() {
+    let a = i(0, 10);
+    assert_eq!(a.shrink_left(4), i(4, 10));
+    assert_eq!(a.shrink_left(-4), a);
+    assert_eq!(a.shrink_left(11), empty());
+    assert_eq!(a.shrink_right(4), i(0, 4));
+    assert_eq!(a.shrink_right(-1), empty());
+    assert_eq!(a.strict_shrink_left(4), i(5, 10));
+    assert_eq!(a.strict_shrink_left(10), empty());
+    assert_eq!(a.strict_shrink_right(0), empty());
+    assert_eq!(a.strict_shrink_right(4), i(0, 3));
+    assert_eq!(empty().shrink_left(0), empty());
+    assert_eq!(empty().strict_shrink_right(0), empty());
+    assert_eq!(Interval::<u8>::whole().strict_shrink_right(0), Interval::empty());
+    assert_eq!(Interval::<u8>::whole().strict_shrink_left(u8::MAX), Interval::empty());
+    assert_eq!(Interval::<i8>::whole().strict_shrink_left(i8::MIN), Interval::whole());
+  }
+
+  #[test]
+  fn set_test() {
+    for a in test_data() {
+      for x in -6..10 {
+        assert_eq!(a.contains(&x), to_set(&a).contains(&x), "{} contains {}", a, x);
+        assert!(a.contains(&x) || a.complement().contains(&x), "{} is neither in {} nor in its complement", x, a);
+      }
+      for b in test_data() {
+        let (sa, sb) = (to_set(&a), to_set(&b));
+        assert_eq!(a.is_disjoint(&b), sa.is_disjoint(&sb), "{} disjoint {}", a, b);
+        assert_eq!(a.overlap(&b), sa.overlap(&sb), "{} overlap {}", a, b);
+        assert_eq!(a.is_subset(&b), sa.is_subset(&sb), "{} subset {}", a, b);
+        assert_eq!(a.is_proper_subset(&b), sa.is_proper_subset(&sb), "{} proper subset {}", a, b);
+        assert_eq!(a.intersection(&b), hull(sa.intersection(&sb)), "{} intersection {}", a, b);
+        assert_eq!(a.union(&b), hull(sa.union(&sb)), "{} union {}", a, b);
+        assert_eq!(a.difference(&b), hull(sa.difference(&sb)), "{} difference {}", a, b);
+        assert_eq!(a.symmetric_difference(&b), hull(sa.symmetric_difference(&sb)),
+          "{} symmetric difference {}", a, b);
+      }
+    }
+    assert_eq!(i(0, 10).complement(), Interval::whole());
+    assert_eq!(i(0, i32::MAX).complement(), i(-i32::MAX, -1));
+    assert_eq!(Interval::<i32>::whole().complement(), empty());
+    assert_eq!(empty().complement(), Interval::whole());
+    MaySetTester::new(0, test_data(), (-6..10).collect()).test_all();
+  }
+
+  #[test]
+  fn lattice_test() {
+    let data_a =          vec![empty(), i(1, 2),  i(0, 5), i(1, 2), i(0, 3), i(0, 0)];
+    let data_b =          vec![i(1, 2), empty(),  i(1, 2), i(4, 6), i(2, 5), Interval::whole()];
+    let expected_entail = vec![False,   True,     True,    Unknown, Unknown, False];
+    let expected_join =   vec![i(1, 2), i(1, 2),  i(0, 5), i(1, 6), i(0, 5), Interval::whole()];
+    let expected_meet =   vec![empty(), empty(),  i(1, 2), empty(), i(2, 3), i(0, 0)];
+    LatticeTester::new(0, data_a, data_b, expected_entail, expected_join, expected_meet).test_all();
+    RandomLatticeTester::<Interval<i8>, _>::arbitrary(1).seed(test_seed(1)).test_all();
+    RandomLatticeTester::<Interval<u16>, _>::arbitrary(2).seed(test_seed(2)).test_all();
+  }
+
+  #[test]
+  fn widening_test() {
+    let tester = WideningTester::new(0, vec![i(0, 1), i(2, 3), empty()], vec![i(-1, 1), i(2, 4), i(0, 0)]);
+    tester.test_chain(20, 1, |n| i(0, n as i32));
+    tester.test_chain(20, 1, |n| i(-(n as i32), 0));
+    tester.test_all();
+    assert_eq!(i(0, 1).widen(i(0, 2)), i(0, i32::MAX));
+    assert_eq!(i(0, i32::MAX).narrow(i(0, 10)), i(0, 10));
+    assert_eq!(i(0, 5).narrow(i(0, 10)), i(0, 5));
+  }
+}
@@ -30,13 +30,23 @@ pub mod ops;
 pub mod wrappers;
 pub mod queue;
 pub mod stack;
+pub mod fixpoint;
+pub mod crdt;
+pub mod lvar;
+pub mod interval;
 
 pub use kind::*;
 pub use queue::*;
 pub use stack::*;
+pub use wrappers::hash_map::*;
 pub use wrappers::hash_set::*;
+pub use wrappers::btree_map::*;
 pub use wrappers::btree_set::*;
 pub use wrappers::bit_set::*;
 pub use wrappers::optional::*;
+pub use wrappers::order::*;
+pub use wrappers::powerset::*;
+pub use wrappers::sparse_set::*;
 pub use wrappers::vector::*;
 pub use wrappers::vector_deque::*;
+pub use interval::*;
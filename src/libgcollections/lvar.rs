@@ -0,0 +1,204 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Lattice variables ([LVars](https://dl.acm.org/doi/10.1145/2502323.2502326)) are cells shared between threads whose value only moves up in a lattice.
+//!
+//! A thread writes into the variable with `put`, which joins the new information with the current value.
+//! Since the join is commutative, the final value does not depend on the order of the writes.
+//! A thread reads the variable with a threshold: `read_threshold` blocks until the value entails the threshold, and `try_read_threshold` returns immediately.
+//! Once a threshold is reached it stays reached, thus the outcome of a threshold read does not depend on the scheduling of the threads (except for how long it blocks).
+//!
+//! ```rust
+//! use gcollections::ops::*;
+//! use gcollections::lvar::LVar;
+//! use gcollections::Max;
+//! use std::sync::Arc;
+//! use std::thread;
+//!
+//! let bound = Arc::new(LVar::new(Max::wrap(0)));
+//! let workers: Vec<_> = (1..5).map(|i| {
+//!   let bound = bound.clone();
+//!   thread::spawn(move || { bound.put(Max::wrap(i * 10)); })
+//! }).collect();
+//! bound.read_threshold(&Max::wrap(40));
+//! for worker in workers { worker.join().unwrap(); }
+//! ```
+
+use ops::*;
+use trilean::SKleene;
+use trilean::SKleene::*;
+use std::sync::{Mutex, MutexGuard, Condvar, PoisonError};
+
+pub struct LVar<L>
+{
+  value: Mutex<L>,
+  changed: Condvar
+}
+
+/// Only the join and the entailment are used, thus the value can be any join-semilattice such as the state of a CRDT.
+impl<L> LVar<L> where
+ L: JoinSemilattice + Bot + Clone
+{
+  pub fn new(value: L) -> LVar<L> {
+    LVar {
+      value: Mutex::new(value),
+      changed: Condvar::new()
+    }
+  }
+
+  /// The variable starts with no information.
+  pub fn bot() -> LVar<L> {
+    LVar::new(L::bot())
+  }
+
+  /// The value is always replaced by a complete join (see `JoinAssign`), so it stays valid even if a thread panicked while holding the lock.
+  fn lock<'a>(&'a self) -> MutexGuard<'a, L> {
+    self.value.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+
+  /// Joins `value` with the current value, and returns `true` if it has changed.
+  /// The threads blocked in `read_threshold` are woken up if the value has changed.
+  pub fn put(&self, value: L) -> bool {
+    let changed = self.lock().join_assign(value);
+    if changed {
+      self.changed.notify_all();
+    }
+    changed
+  }
+
+  /// Non-blocking threshold read: `True` if the value entails `threshold`.
+  /// Otherwise, the threshold is not reached yet: `False` if the value is below the threshold, and `Unknown` if they are incomparable (the threshold can only be reached by joining information not yet below it).
+  pub fn try_read_threshold(&self, threshold: &L) -> SKleene {
+    self.lock().entail(threshold)
+  }
+
+  /// Blocks until the value entails `threshold`.
+  pub fn read_threshold(&self, threshold: &L) {
+    let mut value = self.lock();
+    while value.entail(threshold) != True {
+      value = self.changed.wait(value).unwrap_or_else(PoisonError::into_inner);
+    }
+  }
+
+  /// Blocks until the value entails one of the `thresholds` and returns the index of the first one entailed.
+  /// The result is deterministic if the thresholds are pairwise incompatible, which means that their join is `top`.
+  pub fn read_thresholds(&self, thresholds: &[L]) -> usize {
+    let mut value = self.lock();
+    loop {
+      if let Some(i) = thresholds.iter().position(|t| value.entail(t) == True) {
+        return i;
+      }
+      value = self.changed.wait(value).unwrap_or_else(PoisonError::into_inner);
+    }
+  }
+
+  /// Current value of the variable, it depends on the scheduling of the threads.
+  /// For example, a search worker can use it to prune with the best bound found so far.
+  pub fn snapshot(&self) -> L {
+    self.lock().clone()
+  }
+
+  /// Final value once all the threads are done with the variable.
+  pub fn into_inner(self) -> L {
+    self.value.into_inner().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use wrappers::btree_set::BTreeSet;
+  use wrappers::order::Max;
+  use crdt::counter::GCounter;
+  use std::sync::Arc;
+  use std::thread;
+
+  fn set(values: &[u32]) -> BTreeSet<u32> {
+    BTreeSet::wrap(values.iter().cloned().collect())
+  }
+
+  #[test]
+  fn put_test() {
+    let x = LVar::new(Max::wrap(2));
+    assert!(x.put(Max::wrap(5)));
+    assert!(!x.put(Max::wrap(3)));
+    assert!(!x.put(Max::wrap(5)));
+    assert_eq!(x.snapshot(), Max::wrap(5));
+    assert_eq!(x.try_read_threshold(&Max::wrap(4)), True);
+    assert_eq!(x.try_read_threshold(&Max::wrap(6)), False);
+    assert_eq!(x.into_inner(), Max::wrap(5));
+
+    let s = LVar::new(set(&[1]));
+    assert_eq!(s.try_read_threshold(&set(&[2])), Unknown);
+    s.put(set(&[2]));
+    assert_eq!(s.try_read_threshold(&set(&[2])), True);
+  }
+
+  #[test]
+  fn threads_test() {
+    let shared = Arc::new(LVar::new(set(&[])));
+    let writers: Vec<_> = (0..8).map(|i| {
+      let shared = shared.clone();
+      thread::spawn(move || {
+        for j in 0..10 {
+          shared.put(set(&[i * 10 + j]));
+        }
+      })
+    }).collect();
+    let readers: Vec<_> = (0..4).map(|i| {
+      let shared = shared.clone();
+      thread::spawn(move || {
+        shared.read_threshold(&set(&[i, 79]));
+        shared.try_read_threshold(&set(&[i, 79]))
+      })
+    }).collect();
+    for writer in writers {
+      writer.join().unwrap();
+    }
+    for reader in readers {
+      assert_eq!(reader.join().unwrap(), True);
+    }
+    let shared = Arc::try_unwrap(shared).ok().unwrap();
+    assert_eq!(shared.into_inner(), BTreeSet::wrap((0..80).collect()));
+  }
+
+  #[test]
+  fn read_thresholds_test() {
+    let bound = Arc::new(LVar::new(Max::wrap(0u64)));
+    let reader = {
+      let bound = bound.clone();
+      thread::spawn(move || bound.read_thresholds(&[Max::wrap(1000), Max::wrap(100)]))
+    };
+    for i in 0..200 {
+      bound.put(Max::wrap(i));
+    }
+    // The first threshold is never reached, so the reader can only return the second one.
+    assert_eq!(reader.join().unwrap(), 1);
+  }
+
+  #[test]
+  fn join_semilattice_test() {
+    // A grow-only counter has no meet, each thread increments its own entry.
+    let counter = Arc::new(LVar::<GCounter>::bot());
+    let writers: Vec<_> = (0..4).map(|replica| {
+      let counter = counter.clone();
+      thread::spawn(move || {
+        let mut local = GCounter::new();
+        for _ in 0..10 {
+          local.increment(replica);
+          counter.put(local.clone());
+        }
+      })
+    }).collect();
+    for writer in writers {
+      writer.join().unwrap();
+    }
+    let counter = Arc::try_unwrap(counter).ok().unwrap();
+    assert_eq!(counter.into_inner().value(), 40);
+  }
+}
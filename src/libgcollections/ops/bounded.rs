@@ -24,6 +24,39 @@ pub trait ShrinkRight: Bounded {
   fn shrink_right(&self, ub: Self::Item) -> Self;
 }
 
+/// Next and previous representable values of a floating-point bound, used by `FloatInterval` to shrink strictly.
+/// `NaN` is its own successor and predecessor.
+pub trait Successor: Sized {
+  /// `None` if `self` is the largest value of its type.
+  fn successor(&self) -> Option<Self>;
+  /// `None` if `self` is the smallest value of its type.
+  fn predecessor(&self) -> Option<Self>;
+}
+
+macro_rules! float_successor_impl
+{
+  ( $( $t:ty ),* ) =>
+  {$(
+    /// The successor of the largest finite value is the infinity, and the infinity has no successor.
+    impl Successor for $t
+    {
+      fn successor(&self) -> Option<$t> {
+        if self.is_nan() { Some(*self) }
+        else if *self == <$t>::INFINITY { None }
+        else if *self == 0.0 { Some(<$t>::from_bits(1)) }
+        else if *self > 0.0 { Some(<$t>::from_bits(self.to_bits() + 1)) }
+        else { Some(<$t>::from_bits(self.to_bits() - 1)) }
+      }
+
+      fn predecessor(&self) -> Option<$t> {
+        (-*self).successor().map(|x| -x)
+      }
+    }
+  )*}
+}
+
+float_successor_impl!(f32, f64);
+
 pub trait StrictShrinkLeft: Bounded {
   fn strict_shrink_left(&self, lb: Self::Item) -> Self;
 }
@@ -0,0 +1,396 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Combinators building a lattice from another lattice or from any type:
+//!
+//! * `Dual<L>` reverses the order of `L`.
+//! * `Lifted<L>` adds a fresh bottom element below `L`.
+//! * `WithTop<L>` adds a fresh top element above `L`.
+//! * `Flat<T>` is the flat lattice over `T`: all the values are incomparable, with a bottom element below and a top element above all of them.
+
+use ops::lattice::*;
+use trilean::SKleene::*;
+use std::ops::{Deref, DerefMut};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Dual<L>
+{
+  value: L
+}
+
+impl<L> Dual<L>
+{
+  pub fn wrap(value: L) -> Dual<L> {
+    Dual {
+      value: value
+    }
+  }
+
+  pub fn unwrap(self) -> L {
+    self.value
+  }
+}
+
+impl<L> Deref for Dual<L>
+{
+  type Target = L;
+
+  fn deref<'a>(&'a self) -> &'a L {
+    &self.value
+  }
+}
+
+impl<L> DerefMut for Dual<L>
+{
+  fn deref_mut<'a>(&'a mut self) -> &'a mut L {
+    &mut self.value
+  }
+}
+
+impl<L: Meet> Join for Dual<L>
+{
+  fn join(self, other: Dual<L>) -> Dual<L> {
+    Dual::wrap(self.value.meet(other.value))
+  }
+}
+
+impl<L: Join> Meet for Dual<L>
+{
+  fn meet(self, other: Dual<L>) -> Dual<L> {
+    Dual::wrap(self.value.join(other.value))
+  }
+}
+
+impl<L: Entailment> Entailment for Dual<L>
+{
+  fn entail(&self, other: &Dual<L>) -> SKleene {
+    other.value.entail(&self.value)
+  }
+}
+
+impl<L: Bot> Top for Dual<L>
+{
+  fn top() -> Dual<L> {
+    Dual::wrap(L::bot())
+  }
+}
+
+impl<L: Top> Bot for Dual<L>
+{
+  fn bot() -> Dual<L> {
+    Dual::wrap(L::top())
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Lifted<L>
+{
+  Bottom,
+  Value(L)
+}
+
+impl<L: Join> Join for Lifted<L>
+{
+  fn join(self, other: Lifted<L>) -> Lifted<L> {
+    use self::Lifted::*;
+    match (self, other) {
+      (Bottom, x) | (x, Bottom) => x,
+      (Value(x), Value(y)) => Value(x.join(y))
+    }
+  }
+}
+
+impl<L: Meet> Meet for Lifted<L>
+{
+  fn meet(self, other: Lifted<L>) -> Lifted<L> {
+    use self::Lifted::*;
+    match (self, other) {
+      (Value(x), Value(y)) => Value(x.meet(y)),
+      _ => Bottom
+    }
+  }
+}
+
+impl<L: Entailment> Entailment for Lifted<L>
+{
+  fn entail(&self, other: &Lifted<L>) -> SKleene {
+    use self::Lifted::*;
+    match (self, other) {
+      (_, Bottom) => True,
+      (Bottom, _) => False,
+      (Value(x), Value(y)) => x.entail(y)
+    }
+  }
+}
+
+impl<L: Top> Top for Lifted<L>
+{
+  fn top() -> Lifted<L> {
+    Lifted::Value(L::top())
+  }
+}
+
+impl<L> Bot for Lifted<L>
+{
+  fn bot() -> Lifted<L> {
+    Lifted::Bottom
+  }
+}
+
+impl<L: Widen> Widen for Lifted<L>
+{
+  fn widen(self, other: Lifted<L>) -> Lifted<L> {
+    use self::Lifted::*;
+    match (self, other) {
+      (Bottom, x) | (x, Bottom) => x,
+      (Value(x), Value(y)) => Value(x.widen(y))
+    }
+  }
+}
+
+impl<L: Narrow> Narrow for Lifted<L>
+{
+  fn narrow(self, other: Lifted<L>) -> Lifted<L> {
+    use self::Lifted::*;
+    match (self, other) {
+      (Value(x), Value(y)) => Value(x.narrow(y)),
+      _ => Bottom
+    }
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WithTop<L>
+{
+  Value(L),
+  Topmost
+}
+
+impl<L: Join> Join for WithTop<L>
+{
+  fn join(self, other: WithTop<L>) -> WithTop<L> {
+    use self::WithTop::*;
+    match (self, other) {
+      (Value(x), Value(y)) => Value(x.join(y)),
+      _ => Topmost
+    }
+  }
+}
+
+impl<L: Meet> Meet for WithTop<L>
+{
+  fn meet(self, other: WithTop<L>) -> WithTop<L> {
+    use self::WithTop::*;
+    match (self, other) {
+      (Topmost, x) | (x, Topmost) => x,
+      (Value(x), Value(y)) => Value(x.meet(y))
+    }
+  }
+}
+
+impl<L: Entailment> Entailment for WithTop<L>
+{
+  fn entail(&self, other: &WithTop<L>) -> SKleene {
+    use self::WithTop::*;
+    match (self, other) {
+      (Topmost, _) => True,
+      (_, Topmost) => False,
+      (Value(x), Value(y)) => x.entail(y)
+    }
+  }
+}
+
+impl<L> Top for WithTop<L>
+{
+  fn top() -> WithTop<L> {
+    WithTop::Topmost
+  }
+}
+
+impl<L: Bot> Bot for WithTop<L>
+{
+  fn bot() -> WithTop<L> {
+    WithTop::Value(L::bot())
+  }
+}
+
+impl<L: Widen> Widen for WithTop<L>
+{
+  fn widen(self, other: WithTop<L>) -> WithTop<L> {
+    use self::WithTop::*;
+    match (self, other) {
+      (Value(x), Value(y)) => Value(x.widen(y)),
+      _ => Topmost
+    }
+  }
+}
+
+impl<L: Narrow> Narrow for WithTop<L>
+{
+  fn narrow(self, other: WithTop<L>) -> WithTop<L> {
+    use self::WithTop::*;
+    match (self, other) {
+      (Topmost, x) | (x, Topmost) => x,
+      (Value(x), Value(y)) => Value(x.narrow(y))
+    }
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Flat<T>
+{
+  Bottom,
+  Value(T),
+  Topmost
+}
+
+impl<T: Eq> Join for Flat<T>
+{
+  fn join(self, other: Flat<T>) -> Flat<T> {
+    use self::Flat::*;
+    match (self, other) {
+      (Bottom, x) | (x, Bottom) => x,
+      (Value(x), Value(y)) if x == y => Value(x),
+      _ => Topmost
+    }
+  }
+
+  /// The value is not copied, it is only replaced when it changes.
+  fn join_in_place(&mut self, other: Flat<T>) -> bool {
+    use self::Flat::*;
+    let joined = match (&*self, other) {
+      (_, Bottom) | (&Topmost, _) => return false,
+      (Value(x), Value(y)) if *x == y => return false,
+      (&Bottom, y) => y,
+      _ => Topmost
+    };
+    *self = joined;
+    true
+  }
+}
+
+impl<T: Eq> Meet for Flat<T>
+{
+  fn meet(self, other: Flat<T>) -> Flat<T> {
+    use self::Flat::*;
+    match (self, other) {
+      (Topmost, x) | (x, Topmost) => x,
+      (Value(x), Value(y)) if x == y => Value(x),
+      _ => Bottom
+    }
+  }
+
+  fn meet_in_place(&mut self, other: Flat<T>) -> bool {
+    use self::Flat::*;
+    let met = match (&*self, other) {
+      (_, Topmost) | (&Bottom, _) => return false,
+      (Value(x), Value(y)) if *x == y => return false,
+      (&Topmost, y) => y,
+      _ => Bottom
+    };
+    *self = met;
+    true
+  }
+}
+
+impl<T: Eq> Entailment for Flat<T>
+{
+  fn entail(&self, other: &Flat<T>) -> SKleene {
+    use self::Flat::*;
+    match (self, other) {
+      (_, Bottom) | (Topmost, _) => True,
+      (Bottom, _) | (_, Topmost) => False,
+      (Value(x), Value(y)) => if x == y { True } else { Unknown }
+    }
+  }
+}
+
+impl<T> Top for Flat<T>
+{
+  fn top() -> Flat<T> {
+    Flat::Topmost
+  }
+}
+
+impl<T> Bot for Flat<T>
+{
+  fn bot() -> Flat<T> {
+    Flat::Bottom
+  }
+}
+
+impl<T: Eq> Widen for Flat<T> {}
+impl<T: Eq> Narrow for Flat<T> {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ops::lattice::test::*;
+  use self::Flat::*;
+
+  #[test]
+  fn flat_test() {
+    let data_a =          vec![Bottom, Bottom,   Value(1), Value(1), Value(1), Value(2), Topmost];
+    let data_b =          vec![Bottom, Value(1), Value(1), Value(2), Topmost,  Bottom,   Value(2)];
+    let expected_entail = vec![True,   False,    True,     Unknown,  False,    True,     True];
+    let expected_join =   vec![Bottom, Value(1), Value(1), Topmost,  Topmost,  Value(2), Topmost];
+    let expected_meet =   vec![Bottom, Bottom,   Value(1), Bottom,   Value(1), Bottom,   Value(2)];
+    let data_c =          vec![Value(1), Value(2), Value(3), Value(3), Bottom,   Topmost,  Value(1)];
+    LatticeTester::new(0, data_a.clone(), data_b.clone(), expected_entail, expected_join, expected_meet)
+      .with_data_c(data_c)
+      .modular()
+      .test_all();
+    let widening = WideningTester::new(0, data_a, data_b);
+    widening.test_chain(10, 1, |i| Value(i % 3));
+    widening.test_all();
+  }
+
+  #[test]
+  #[should_panic(expected = "distributivity")]
+  fn flat_not_distributive_test() {
+    LatticeTester::new(0, vec![Value(1)], vec![Value(2)], vec![Unknown], vec![Topmost], vec![Bottom])
+      .with_data_c(vec![Value(3)])
+      .distributive()
+      .test_all();
+  }
+
+  #[test]
+  fn dual_test() {
+    let d = Dual::wrap;
+    let data_a =          vec![d(Bottom), d(Bottom),   d(Value(1)), d(Value(1)), d(Value(1)), d(Topmost)];
+    let data_b =          vec![d(Bottom), d(Value(1)), d(Value(1)), d(Value(2)), d(Topmost),  d(Value(2))];
+    let expected_entail = vec![True,      True,        True,        Unknown,     True,        False];
+    let expected_join =   vec![d(Bottom), d(Bottom),   d(Value(1)), d(Bottom),   d(Value(1)), d(Value(2))];
+    let expected_meet =   vec![d(Bottom), d(Value(1)), d(Value(1)), d(Topmost),  d(Topmost),  d(Topmost)];
+    LatticeTester::new(1, data_a, data_b, expected_entail, expected_join, expected_meet).test_all();
+  }
+
+  #[test]
+  fn lifted_test() {
+    use super::Lifted::Bottom as New;
+    use super::Lifted::Value as V;
+    let data_a =          vec![New, New,       V(Bottom), V(Value(1)), V(Value(1)), V(Topmost)];
+    let data_b =          vec![New, V(Bottom), V(Bottom), New,         V(Value(2)), V(Value(2))];
+    let expected_entail = vec![True, False,    True,      True,        Unknown,     True];
+    let expected_join =   vec![New, V(Bottom), V(Bottom), V(Value(1)), V(Topmost),  V(Topmost)];
+    let expected_meet =   vec![New, New,       V(Bottom), New,         V(Bottom),   V(Value(2))];
+    LatticeTester::new(2, data_a, data_b, expected_entail, expected_join, expected_meet).test_all();
+  }
+
+  #[test]
+  fn with_top_test() {
+    use super::WithTop::Topmost as New;
+    use super::WithTop::Value as V;
+    let data_a =          vec![New, New,        V(Topmost), V(Value(1)), V(Value(1)), V(Bottom)];
+    let data_b =          vec![New, V(Topmost), V(Topmost), New,         V(Value(2)), V(Value(2))];
+    let expected_entail = vec![True, True,      True,       False,       Unknown,     False];
+    let expected_join =   vec![New, New,        V(Topmost), New,         V(Topmost),  V(Value(2))];
+    let expected_meet =   vec![New, V(Topmost), V(Topmost), V(Value(1)), V(Bottom),   V(Bottom)];
+    LatticeTester::new(3, data_a, data_b, expected_entail, expected_join, expected_meet).test_all();
+  }
+}
@@ -0,0 +1,19 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A [Galois connection](https://en.wikipedia.org/wiki/Galois_connection) relates a concrete lattice `C` to an abstract lattice `A`.
+//! The abstraction `α` and the concretization `γ` must be monotone and satisfy `α(c) ⊑ a ⇔ c ⊑ γ(a)`, where `x ⊑ y` is `y.entail(&x) == True`.
+//! It means that `α(c)` is the most precise abstract element describing `c`, and that `γ(α(c))` over-approximates `c`.
+//! These properties can be checked with `ops::lattice::test::GaloisTester`.
+
+/// Similarly to `Universe`, the connection is implemented by a type only used at the type level, several connections can thus relate the same lattices.
+pub trait GaloisConnection<C, A>
+{
+  fn abstraction(concrete: &C) -> A;
+  fn concretization(abstract_value: &A) -> C;
+}
@@ -0,0 +1,169 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Lexicographic product of two lattices: `(a1, b1)` is below `(a2, b2)` if `a1` is strictly below `a2`, or if `a1 == a2` and `b1` is below `b2`.
+//! The second component only matters when the first components are equal.
+//!
+//! When the first components are incomparable, the two elements are incomparable (the entailment is `Unknown`), their join is `(a1.join(a2), B::bot())` and their meet is `(a1.meet(a2), B::top())`.
+//! Indeed, `a1.join(a2)` is strictly above `a1` and `a2`, thus every element with this first component is an upper bound and the least one has `bot` as second component.
+//!
+//! `Lex<A, B>` is a lattice if `A` and `B` are lattices and either `A` is a chain (two elements are always comparable) or `B` has a `top` and a `bot`.
+//! If `A` is a chain, the join and meet never use `B::bot()` and `B::top()`.
+//! Otherwise, `Lex<A, B>` is generally not distributive even if `A` and `B` are.
+//!
+//! ```rust
+//! # extern crate gcollections;
+//! # extern crate trilean;
+//! use gcollections::ops::*;
+//! use gcollections::ops::lattice::lex::Lex;
+//! use trilean::SKleene::*;
+//!
+//! let a = Lex::new(Flat::Value(1), Flat::Value(2));
+//! let b = Lex::new(Flat::Value(1), Flat::Bottom);
+//! assert_eq!(a.entail(&b), True);
+//! let c = Lex::new(Flat::Value(2), Flat::Value(2));
+//! assert_eq!(a.entail(&c), Unknown);
+//! assert_eq!(a.join(c), Lex::new(Flat::Topmost, Flat::Bottom));
+//! ```
+
+use ops::lattice::*;
+use trilean::SKleene::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Lex<A, B>
+{
+  pub first: A,
+  pub second: B
+}
+
+impl<A, B> Lex<A, B>
+{
+  pub fn new(first: A, second: B) -> Lex<A, B> {
+    Lex {
+      first: first,
+      second: second
+    }
+  }
+}
+
+enum Comparison
+{
+  Equal,
+  Above,
+  Below,
+  Incomparable
+}
+
+/// Position of `a` relatively to `b`, computed with the entailment in both directions.
+fn compare<A: Entailment>(a: &A, b: &A) -> Comparison {
+  match a.entail(b) {
+    True => if b.entail(a) == True { Comparison::Equal } else { Comparison::Above },
+    False => Comparison::Below,
+    Unknown => Comparison::Incomparable
+  }
+}
+
+impl<A, B> Join for Lex<A, B> where
+ A: Join + Entailment,
+ B: Join + Bot
+{
+  fn join(self, other: Lex<A, B>) -> Lex<A, B> {
+    match compare(&self.first, &other.first) {
+      Comparison::Equal => Lex::new(self.first, self.second.join(other.second)),
+      Comparison::Above => self,
+      Comparison::Below => other,
+      Comparison::Incomparable => Lex::new(self.first.join(other.first), B::bot())
+    }
+  }
+}
+
+impl<A, B> Meet for Lex<A, B> where
+ A: Meet + Entailment,
+ B: Meet + Top
+{
+  fn meet(self, other: Lex<A, B>) -> Lex<A, B> {
+    match compare(&self.first, &other.first) {
+      Comparison::Equal => Lex::new(self.first, self.second.meet(other.second)),
+      Comparison::Above => other,
+      Comparison::Below => self,
+      Comparison::Incomparable => Lex::new(self.first.meet(other.first), B::top())
+    }
+  }
+}
+
+impl<A, B> Entailment for Lex<A, B> where
+ A: Entailment,
+ B: Entailment
+{
+  fn entail(&self, other: &Lex<A, B>) -> SKleene {
+    match compare(&self.first, &other.first) {
+      Comparison::Equal => self.second.entail(&other.second),
+      Comparison::Above => True,
+      Comparison::Below => False,
+      Comparison::Incomparable => Unknown
+    }
+  }
+}
+
+impl<A: Top, B: Top> Top for Lex<A, B>
+{
+  fn top() -> Lex<A, B> {
+    Lex::new(A::top(), B::top())
+  }
+}
+
+impl<A: Bot, B: Bot> Bot for Lex<A, B>
+{
+  fn bot() -> Lex<A, B> {
+    Lex::new(A::bot(), B::bot())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ops::lattice::test::*;
+  use wrappers::order::Max;
+
+  #[test]
+  fn chain_first_test() {
+    // The first component is a chain, so the lexicographic product is a chain as well.
+    let l = |a, b| Lex::new(Max::wrap(a), Flat::Value(b));
+    let data_a =          vec![l(1, 1), l(1, 1),  l(1, 1), l(2, 1), l(1, 1)];
+    let data_b =          vec![l(1, 1), l(1, 2),  l(2, 0), l(1, 5), l(0, 2)];
+    let expected_entail = vec![True,    Unknown,  False,   True,    True];
+    let expected_join =   vec![l(1, 1), Lex::new(Max::wrap(1), Flat::Topmost), l(2, 0), l(2, 1), l(1, 1)];
+    let expected_meet =   vec![l(1, 1), Lex::new(Max::wrap(1), Flat::Bottom),  l(1, 1), l(1, 5), l(0, 2)];
+    LatticeTester::new(0, data_a, data_b, expected_entail, expected_join, expected_meet)
+      .modular()
+      .test_all();
+
+    RandomLatticeTester::new(1, |rng: &mut Rng|
+        Lex::new(Max::wrap(rng.below(4) as i8), Max::wrap(rng.below(4) as i8)))
+      .seed(test_seed(1))
+      .distributive()
+      .test_all();
+  }
+
+  #[test]
+  fn incomparable_first_test() {
+    use ops::lattice::combinators::Flat::*;
+    let l = |a, b| Lex::new(a, Max::wrap(b));
+    let data_a =          vec![l(Value(1), 3), l(Value(1), 3), l(Bottom, 3),   l(Value(1), 3)];
+    let data_b =          vec![l(Value(2), 1), l(Value(1), 5), l(Value(2), 1), l(Topmost, 0)];
+    let expected_entail = vec![Unknown,        False,          False,          False];
+    let expected_join =   vec![l(Topmost, i8::MIN), l(Value(1), 5), l(Value(2), 1), l(Topmost, 0)];
+    let expected_meet =   vec![l(Bottom, i8::MAX),  l(Value(1), 3), l(Bottom, 3),   l(Value(1), 3)];
+    LatticeTester::new(2, data_a, data_b, expected_entail, expected_join, expected_meet).test_all();
+
+    RandomLatticeTester::<Lex<Flat<u8>, Max<i8>>, _>::new(3, |rng: &mut Rng|
+        Lex::new(Flat::arbitrary(rng), Max::wrap(rng.below(4) as i8)))
+      .seed(test_seed(3))
+      .test_all();
+  }
+}
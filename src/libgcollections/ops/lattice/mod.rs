@@ -0,0 +1,906 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// A [lattice](https://en.wikipedia.org/wiki/Lattice_(order)) is an ordered set where every pair of elements can be combined with a `join` and `meet` operations.
+/// The following traits operate on an element of the lattice, and do not represent a set of elements (which would be the whole lattice).
+/// This is the reason why we do not require `Lattice` to inherit from `Collection`.
+///
+/// Many structures only have one of the two operations, for example a set without a universe has no `top` and a grow-only counter has no `meet`.
+/// They are described by `JoinSemilattice` and `MeetSemilattice`, and by their bounded variants `BoundedJoinSemilattice` (with `bot`) and `BoundedMeetSemilattice` (with `top`).
+/// `Lattice` and `BoundedLattice` are implemented for every type implementing both semilattices.
+
+use trilean::SKleene;
+
+pub mod product;
+pub mod combinators;
+pub mod lex;
+pub mod reduced_product;
+pub mod galois;
+
+pub use self::combinators::*;
+pub use self::lex::*;
+pub use self::reduced_product::*;
+pub use self::galois::*;
+
+pub trait Join
+{
+  fn join(self, other: Self) -> Self;
+
+  /// Implementation of `JoinAssign`, collections override it to update `self` in place.
+  /// By default, the join is computed on a copy unless `self` already entails `other`.
+  fn join_in_place(&mut self, other: Self) -> bool where
+    Self: Entailment + Clone
+  {
+    if self.entail(&other) == SKleene::True { false }
+    else {
+      *self = self.clone().join(other);
+      true
+    }
+  }
+}
+
+pub trait Meet
+{
+  fn meet(self, other: Self) -> Self;
+
+  /// Implementation of `MeetAssign`, collections override it to update `self` in place.
+  /// By default, the meet is computed on a copy unless `other` already entails `self`.
+  fn meet_in_place(&mut self, other: Self) -> bool where
+    Self: Entailment + Clone
+  {
+    if other.entail(self) == SKleene::True { false }
+    else {
+      *self = self.clone().meet(other);
+      true
+    }
+  }
+}
+
+/// In-place join, `a.join_assign(b)` sets `a` to `a.join(b)` and returns `true` if `a` has changed.
+/// It is implemented for every type implementing `Join + Entailment + Clone`, so a type provides its own in-place join by overriding `Join::join_in_place` instead of implementing this trait.
+pub trait JoinAssign
+{
+  fn join_assign(&mut self, other: Self) -> bool;
+}
+
+/// In-place meet, `a.meet_assign(b)` sets `a` to `a.meet(b)` and returns `true` if `a` has changed.
+/// It is implemented for every type implementing `Meet + Entailment + Clone`, so a type provides its own in-place meet by overriding `Meet::meet_in_place` instead of implementing this trait.
+pub trait MeetAssign
+{
+  fn meet_assign(&mut self, other: Self) -> bool;
+}
+
+// For an explanation on the macros, see `lib.rs`.
+// They forward to `Join::join_in_place` and `Meet::meet_in_place`, thus the in-place implementations are also used on `stable`.
+
+macro_rules! join_assign_impl
+{
+  ( $( $keyword:tt ),*) =>
+  {
+    impl<R> JoinAssign for R where
+      R: Join + Entailment + Clone
+    {
+      $($keyword)* fn join_assign(&mut self, other: R) -> bool {
+        self.join_in_place(other)
+      }
+    }
+  }
+}
+
+#[cfg(feature = "nightly")]
+join_assign_impl!(default);
+#[cfg(not(feature = "nightly"))]
+join_assign_impl!();
+
+macro_rules! meet_assign_impl
+{
+  ( $( $keyword:tt ),*) =>
+  {
+    impl<R> MeetAssign for R where
+      R: Meet + Entailment + Clone
+    {
+      $($keyword)* fn meet_assign(&mut self, other: R) -> bool {
+        self.meet_in_place(other)
+      }
+    }
+  }
+}
+
+#[cfg(feature = "nightly")]
+meet_assign_impl!(default);
+#[cfg(not(feature = "nightly"))]
+meet_assign_impl!();
+
+/// A widening operator guarantees the termination of increasing iterations over lattices with infinite ascending chains.
+/// `a.widen(b)` must be an upper bound of `a` and `b`, and for every increasing chain `y_0, y_1, ...`, the sequence `x_0 = y_0`, `x_i+1 = x_i.widen(y_i+1)` must be eventually stationary.
+/// The default implementation is the join, which is a widening for the lattices of finite height.
+pub trait Widen: Join + Sized
+{
+  fn widen(self, other: Self) -> Self {
+    self.join(other)
+  }
+}
+
+/// A narrowing operator refines a post-fixpoint obtained by widening.
+/// If `b` is below `a`, `a.narrow(b)` must be between `b` and `a`, and every decreasing sequence `x_i+1 = x_i.narrow(y_i+1)` must be eventually stationary.
+/// The default implementation is the meet, which is a narrowing for the lattices of finite height.
+pub trait Narrow: Meet + Sized
+{
+  fn narrow(self, other: Self) -> Self {
+    self.meet(other)
+  }
+}
+
+pub trait Entailment
+{
+  fn entail(&self, other: &Self) -> SKleene;
+}
+
+pub trait StrictEntailment
+{
+  /// Must be similar to `a.entail(&b) && a != b`.
+  fn strict_entail(&self, other: &Self) -> SKleene;
+}
+
+macro_rules! strict_entailment_impl
+{
+  ( $( $keyword:tt ),*) =>
+  {
+    impl<R> StrictEntailment for R where
+      R: Entailment + Eq
+    {
+      $($keyword)* fn strict_entail(&self, other: &R) -> SKleene {
+        self.entail(other).and(SKleene::from_bool(self != other))
+      }
+    }
+  }
+}
+
+#[cfg(feature = "nightly")]
+strict_entailment_impl!(default);
+#[cfg(not(feature = "nightly"))]
+strict_entailment_impl!();
+
+pub trait Top {
+  fn top() -> Self;
+}
+
+pub trait Bot {
+  fn bot() -> Self;
+}
+
+/// The order is given by the entailment and `a.join(b)` must be the least upper bound of `a` and `b`.
+pub trait JoinSemilattice:
+   Join
+ + Entailment
+{}
+
+impl<R> JoinSemilattice for R where
+ R: Join,
+ R: Entailment,
+{}
+
+/// The order is given by the entailment and `a.meet(b)` must be the greatest lower bound of `a` and `b`.
+pub trait MeetSemilattice:
+   Meet
+ + Entailment
+{}
+
+impl<R> MeetSemilattice for R where
+ R: Meet,
+ R: Entailment,
+{}
+
+pub trait BoundedJoinSemilattice:
+   JoinSemilattice
+ + Bot
+{}
+
+impl<R> BoundedJoinSemilattice for R where
+ R: JoinSemilattice,
+ R: Bot,
+{}
+
+pub trait BoundedMeetSemilattice:
+   MeetSemilattice
+ + Top
+{}
+
+impl<R> BoundedMeetSemilattice for R where
+ R: MeetSemilattice,
+ R: Top,
+{}
+
+pub trait Lattice:
+   JoinSemilattice
+ + MeetSemilattice
+{}
+
+impl<R> Lattice for R where
+ R: JoinSemilattice,
+ R: MeetSemilattice,
+{}
+
+pub trait BoundedLattice:
+   Lattice
+ + BoundedJoinSemilattice
+ + BoundedMeetSemilattice
+{}
+
+impl<R> BoundedLattice for R where
+ R: Lattice,
+ R: BoundedJoinSemilattice,
+ R: BoundedMeetSemilattice,
+{}
+
+pub mod test
+{
+  use super::*;
+  use trilean::SKleene;
+  use trilean::SKleene::*;
+  use std::fmt::Debug;
+
+  pub mod random;
+  pub mod galois;
+
+  pub use self::random::*;
+  pub use self::galois::*;
+
+  /// Tests the lattice operations on the pairs `(data_a[i], data_b[i])` against the expected results, and checks the algebraic laws of lattices.
+  /// The laws involving three elements (associativity, distributivity and modularity) are checked on the triples `(data_a[i], data_b[i], data_c[i])`.
+  /// By default, `data_c[i]` is `data_b[i+1]` (wrapping around), it can be given explicitly with `with_data_c`.
+  /// Distributivity and modularity do not hold in every lattice, so they must be enabled with `distributive` or `modular`.
+  pub struct LatticeTester<T>
+  {
+    test_id: usize,
+    checker: Checker,
+    data_a: Vec<T>,
+    data_b: Vec<T>,
+    data_c: Vec<T>,
+    expected_entailment: Vec<SKleene>,
+    expected_join: Vec<T>,
+    expected_meet: Vec<T>,
+    distributive: bool,
+    modular: bool
+  }
+
+  impl<T> LatticeTester<T> where
+    T: BoundedLattice + Clone + Debug + Eq
+  {
+    pub fn new(test_id: usize, data_a: Vec<T>, data_b: Vec<T>,
+      expected_entailment: Vec<SKleene>,
+      expected_join: Vec<T>,
+      expected_meet: Vec<T>) -> Self
+    {
+      let data_c = default_data_c(&data_b);
+      let tester = LatticeTester {
+        test_id: test_id,
+        checker: Checker::new(),
+        data_a: data_a,
+        data_b: data_b,
+        data_c: data_c,
+        expected_entailment: expected_entailment,
+        expected_join: expected_join,
+        expected_meet: expected_meet,
+        distributive: false,
+        modular: false
+      };
+      tester.verify_input_data();
+      tester
+    }
+
+    /// Without expected results, only the algebraic laws are checked.
+    pub fn from_data(test_id: usize, data_a: Vec<T>, data_b: Vec<T>) -> Self {
+      let expected_entailment = data_a.iter().zip(data_b.iter()).map(|(a, b)| a.entail(b)).collect();
+      let expected_join = data_a.iter().zip(data_b.iter()).map(|(a, b)| a.clone().join(b.clone())).collect();
+      let expected_meet = data_a.iter().zip(data_b.iter()).map(|(a, b)| a.clone().meet(b.clone())).collect();
+      LatticeTester::new(test_id, data_a, data_b, expected_entailment, expected_join, expected_meet)
+    }
+
+    pub fn with_data_c(mut self, data_c: Vec<T>) -> Self {
+      self.data_c = data_c;
+      self.verify_input_data();
+      self
+    }
+
+    /// Also checks the distributive laws (and thus the modular law).
+    pub fn distributive(mut self) -> Self {
+      self.distributive = true;
+      self.modular = true;
+      self
+    }
+
+    /// Also checks the modular law.
+    pub fn modular(mut self) -> Self {
+      self.modular = true;
+      self
+    }
+
+    fn verify_input_data(&self) {
+      assert_eq!(self.data_a.len(), self.data_b.len());
+      assert_eq!(self.data_a.len(), self.data_c.len());
+      assert_eq!(self.data_a.len(), self.expected_entailment.len());
+      assert_eq!(self.data_a.len(), self.expected_join.len());
+      assert_eq!(self.data_a.len(), self.expected_meet.len());
+    }
+
+    pub fn test_all(mut self) {
+      let n = self.data_a.len();
+      for i in 0..n {
+        self.checker.current_test = self.make_test_name(i, "entail");
+        self.test_entailment(self.data_a[i].clone(), self.data_b[i].clone(), self.expected_entailment[i]);
+      }
+      for i in 0..n {
+        self.checker.current_test = self.make_test_name(i, "join");
+        self.test_join(self.data_a[i].clone(), self.data_b[i].clone(), self.expected_join[i].clone());
+      }
+      for i in 0..n {
+        self.checker.current_test = self.make_test_name(i, "meet");
+        self.test_meet(self.data_a[i].clone(), self.data_b[i].clone(), self.expected_meet[i].clone());
+      }
+      for i in 0..n {
+        self.checker.current_test = self.make_test_name(i, "absorption");
+        self.test_absorption(self.data_a[i].clone(), self.data_b[i].clone());
+      }
+      for i in 0..n {
+        self.checker.current_test = self.make_triple_test_name(i, "associativity");
+        self.test_associativity(self.data_a[i].clone(), self.data_b[i].clone(), self.data_c[i].clone());
+      }
+      if self.distributive {
+        for i in 0..n {
+          self.checker.current_test = self.make_triple_test_name(i, "distributivity");
+          self.test_distributivity(self.data_a[i].clone(), self.data_b[i].clone(), self.data_c[i].clone());
+        }
+      }
+      if self.modular {
+        for i in 0..n {
+          self.checker.current_test = self.make_triple_test_name(i, "modularity");
+          self.test_modularity(self.data_a[i].clone(), self.data_b[i].clone(), self.data_c[i].clone());
+        }
+      }
+    }
+
+    fn make_test_name(&self, idx: usize, op: &str) -> String {
+      test_name(self.test_id, idx, op, &self.data_a[idx], &self.data_b[idx])
+    }
+
+    fn make_triple_test_name(&self, idx: usize, op: &str) -> String {
+      triple_test_name(self.test_id, idx, op, &self.data_a[idx], &self.data_b[idx], &self.data_c[idx])
+    }
+
+    pub fn test_entailment(&self, a: T, b: T, expected: SKleene) {
+      self.checker.test_entailment(a, b, expected);
+    }
+
+    pub fn test_join(&self, a: T, b: T, expected: T) {
+      self.checker.test_top_join(a.clone());
+      self.checker.test_join(a, b, expected);
+    }
+
+    pub fn test_meet(&self, a: T, b: T, expected: T) {
+      self.checker.test_bot_meet(a.clone());
+      self.checker.test_meet(a, b, expected);
+    }
+
+    pub fn test_absorption(&self, a: T, b: T) {
+      let checker = &self.checker;
+      checker.test_equality(a.clone().join(a.clone().meet(b.clone())), a.clone(), true,
+        "\n problem (absorption): `a.join(a.meet(b))` != `a`.");
+      checker.test_equality(a.clone().meet(a.clone().join(b.clone())), a.clone(), true,
+        "\n problem (absorption): `a.meet(a.join(b))` != `a`.");
+      checker.test_equality(b.clone().join(b.clone().meet(a.clone())), b.clone(), true,
+        "\n problem (absorption): `b.join(b.meet(a))` != `b`.");
+      checker.test_equality(b.clone().meet(b.clone().join(a)), b, true,
+        "\n problem (absorption): `b.meet(b.join(a))` != `b`.");
+    }
+
+    pub fn test_associativity(&self, a: T, b: T, c: T) {
+      self.checker.test_join_associativity(a.clone(), b.clone(), c.clone());
+      self.checker.test_meet_associativity(a, b, c);
+    }
+
+    pub fn test_distributivity(&self, a: T, b: T, c: T) {
+      let left = a.clone().meet(b.clone().join(c.clone()));
+      let right = a.clone().meet(b.clone()).join(a.clone().meet(c.clone()));
+      self.checker.test_equality(left, right, true,
+        "\n problem (distributivity): `a.meet(b.join(c))` != `a.meet(b).join(a.meet(c))`.");
+      let left = a.clone().join(b.clone().meet(c.clone()));
+      let right = a.clone().join(b).meet(a.join(c));
+      self.checker.test_equality(left, right, true,
+        "\n problem (distributivity): `a.join(b.meet(c))` != `a.join(b).meet(a.join(c))`.");
+    }
+
+    /// If `c |= a`, then `a.join(b.meet(c)) == a.join(b).meet(c)`.
+    /// The law is checked on `(a, b, a.join(c))` so it is never trivially true.
+    pub fn test_modularity(&self, a: T, b: T, c: T) {
+      let c = a.clone().join(c);
+      let left = a.clone().join(b.clone().meet(c.clone()));
+      let right = a.join(b).meet(c);
+      self.checker.test_equality(left, right, true,
+        "\n problem (modularity): `a.join(b.meet(c))` != `a.join(b).meet(c)` with `c = a.join(c)`.");
+    }
+  }
+
+  /// Tests the join and the entailment of a join-semilattice on the pairs `(data_a[i], data_b[i])` against the expected results.
+  /// Associativity is checked on the triples `(data_a[i], data_b[i], data_c[i])`, see `LatticeTester` for the default value of `data_c`.
+  pub struct JoinSemilatticeTester<T>
+  {
+    test_id: usize,
+    checker: Checker,
+    data_a: Vec<T>,
+    data_b: Vec<T>,
+    data_c: Vec<T>,
+    expected_entailment: Vec<SKleene>,
+    expected_join: Vec<T>
+  }
+
+  impl<T> JoinSemilatticeTester<T> where
+    T: BoundedJoinSemilattice + Clone + Debug + Eq
+  {
+    pub fn new(test_id: usize, data_a: Vec<T>, data_b: Vec<T>,
+      expected_entailment: Vec<SKleene>,
+      expected_join: Vec<T>) -> Self
+    {
+      let data_c = default_data_c(&data_b);
+      let tester = JoinSemilatticeTester {
+        test_id: test_id,
+        checker: Checker::new(),
+        data_a: data_a,
+        data_b: data_b,
+        data_c: data_c,
+        expected_entailment: expected_entailment,
+        expected_join: expected_join
+      };
+      tester.verify_input_data();
+      tester
+    }
+
+    /// Without expected results, only the algebraic laws are checked.
+    pub fn from_data(test_id: usize, data_a: Vec<T>, data_b: Vec<T>) -> Self {
+      let expected_entailment = data_a.iter().zip(data_b.iter()).map(|(a, b)| a.entail(b)).collect();
+      let expected_join = data_a.iter().zip(data_b.iter()).map(|(a, b)| a.clone().join(b.clone())).collect();
+      JoinSemilatticeTester::new(test_id, data_a, data_b, expected_entailment, expected_join)
+    }
+
+    pub fn with_data_c(mut self, data_c: Vec<T>) -> Self {
+      self.data_c = data_c;
+      self.verify_input_data();
+      self
+    }
+
+    fn verify_input_data(&self) {
+      assert_eq!(self.data_a.len(), self.data_b.len());
+      assert_eq!(self.data_a.len(), self.data_c.len());
+      assert_eq!(self.data_a.len(), self.expected_entailment.len());
+      assert_eq!(self.data_a.len(), self.expected_join.len());
+    }
+
+    pub fn test_all(mut self) {
+      let n = self.data_a.len();
+      for i in 0..n {
+        self.checker.current_test = test_name(self.test_id, i, "entail", &self.data_a[i], &self.data_b[i]);
+        self.test_entailment(self.data_a[i].clone(), self.data_b[i].clone(), self.expected_entailment[i]);
+      }
+      for i in 0..n {
+        self.checker.current_test = test_name(self.test_id, i, "join", &self.data_a[i], &self.data_b[i]);
+        self.test_join(self.data_a[i].clone(), self.data_b[i].clone(), self.expected_join[i].clone());
+      }
+      for i in 0..n {
+        self.checker.current_test = triple_test_name(self.test_id, i, "associativity",
+          &self.data_a[i], &self.data_b[i], &self.data_c[i]);
+        self.test_associativity(self.data_a[i].clone(), self.data_b[i].clone(), self.data_c[i].clone());
+      }
+    }
+
+    pub fn test_entailment(&self, a: T, b: T, expected: SKleene) {
+      self.checker.test_entailment(a, b, expected);
+    }
+
+    pub fn test_join(&self, a: T, b: T, expected: T) {
+      self.checker.test_join(a, b, expected);
+    }
+
+    pub fn test_associativity(&self, a: T, b: T, c: T) {
+      self.checker.test_join_associativity(a, b, c);
+    }
+  }
+
+  /// Tests the meet and the entailment of a meet-semilattice, this is the dual of `JoinSemilatticeTester`.
+  pub struct MeetSemilatticeTester<T>
+  {
+    test_id: usize,
+    checker: Checker,
+    data_a: Vec<T>,
+    data_b: Vec<T>,
+    data_c: Vec<T>,
+    expected_entailment: Vec<SKleene>,
+    expected_meet: Vec<T>
+  }
+
+  impl<T> MeetSemilatticeTester<T> where
+    T: BoundedMeetSemilattice + Clone + Debug + Eq
+  {
+    pub fn new(test_id: usize, data_a: Vec<T>, data_b: Vec<T>,
+      expected_entailment: Vec<SKleene>,
+      expected_meet: Vec<T>) -> Self
+    {
+      let data_c = default_data_c(&data_b);
+      let tester = MeetSemilatticeTester {
+        test_id: test_id,
+        checker: Checker::new(),
+        data_a: data_a,
+        data_b: data_b,
+        data_c: data_c,
+        expected_entailment: expected_entailment,
+        expected_meet: expected_meet
+      };
+      tester.verify_input_data();
+      tester
+    }
+
+    /// Without expected results, only the algebraic laws are checked.
+    pub fn from_data(test_id: usize, data_a: Vec<T>, data_b: Vec<T>) -> Self {
+      let expected_entailment = data_a.iter().zip(data_b.iter()).map(|(a, b)| a.entail(b)).collect();
+      let expected_meet = data_a.iter().zip(data_b.iter()).map(|(a, b)| a.clone().meet(b.clone())).collect();
+      MeetSemilatticeTester::new(test_id, data_a, data_b, expected_entailment, expected_meet)
+    }
+
+    pub fn with_data_c(mut self, data_c: Vec<T>) -> Self {
+      self.data_c = data_c;
+      self.verify_input_data();
+      self
+    }
+
+    fn verify_input_data(&self) {
+      assert_eq!(self.data_a.len(), self.data_b.len());
+      assert_eq!(self.data_a.len(), self.data_c.len());
+      assert_eq!(self.data_a.len(), self.expected_entailment.len());
+      assert_eq!(self.data_a.len(), self.expected_meet.len());
+    }
+
+    pub fn test_all(mut self) {
+      let n = self.data_a.len();
+      for i in 0..n {
+        self.checker.current_test = test_name(self.test_id, i, "entail", &self.data_a[i], &self.data_b[i]);
+        self.test_entailment(self.data_a[i].clone(), self.data_b[i].clone(), self.expected_entailment[i]);
+      }
+      for i in 0..n {
+        self.checker.current_test = test_name(self.test_id, i, "meet", &self.data_a[i], &self.data_b[i]);
+        self.test_meet(self.data_a[i].clone(), self.data_b[i].clone(), self.expected_meet[i].clone());
+      }
+      for i in 0..n {
+        self.checker.current_test = triple_test_name(self.test_id, i, "associativity",
+          &self.data_a[i], &self.data_b[i], &self.data_c[i]);
+        self.test_associativity(self.data_a[i].clone(), self.data_b[i].clone(), self.data_c[i].clone());
+      }
+    }
+
+    pub fn test_entailment(&self, a: T, b: T, expected: SKleene) {
+      self.checker.test_entailment(a, b, expected);
+    }
+
+    pub fn test_meet(&self, a: T, b: T, expected: T) {
+      self.checker.test_meet(a, b, expected);
+    }
+
+    pub fn test_associativity(&self, a: T, b: T, c: T) {
+      self.checker.test_meet_associativity(a, b, c);
+    }
+  }
+
+  fn default_data_c<T: Clone>(data_b: &[T]) -> Vec<T> {
+    let n = data_b.len();
+    (0..n).map(|i| data_b[(i + 1) % n].clone()).collect()
+  }
+
+  fn test_name<T: Debug>(test_id: usize, idx: usize, op: &str, a: &T, b: &T) -> String {
+    format!("[{}] `{}`-test on the data with the index {} has failed.\n\
+               a = {:?}\n\
+               b = {:?}\n\
+               reason: ", test_id, op, idx, a, b)
+  }
+
+  fn triple_test_name<T: Debug>(test_id: usize, idx: usize, op: &str, a: &T, b: &T, c: &T) -> String {
+    format!("[{}] `{}`-test on the data with the index {} has failed.\n\
+               a = {:?}\n\
+               b = {:?}\n\
+               c = {:?}\n\
+               reason: ", test_id, op, idx, a, b, c)
+  }
+
+  /// Assertions shared by the testers, the error messages start with the name of the current test.
+  struct Checker
+  {
+    current_test: String
+  }
+
+  impl Checker
+  {
+    fn new() -> Checker {
+      Checker {
+        current_test: String::new()
+      }
+    }
+
+    fn test_entailment<T>(&self, a: T, b: T, expected: SKleene) where
+      T: Entailment + Clone + Debug + Eq
+    {
+      let ab = a.entail(&b);
+      self.assert_expected("a.entail(b)", ab.clone(), expected, "");
+      let ba = b.entail(&a);
+      match ab {
+        True => {
+          assert!(ba != Unknown,
+            "{}`a.entail(b) == True` and `b.entail(a) == Unknown` \n\
+             problem: if `a |= b` holds, then `b |= a` is either true (a = b) or false.\n\
+             note: `a` and `b` form a chain and can not be unordered.",
+            self.current_test);
+          match ba {
+            True => self.test_equality(a, b, true,
+              "\n problem: if `b |= a` is `true` then we must have `a == b`."),
+            False => self.test_strict_entail(a, b, True),
+            Unknown => self.test_strict_entail(a, b, Unknown)
+          }
+        },
+        False | Unknown => {
+          self.assert_expected("b |= a", ba, !ab,
+            format!("\n  problem: if `a |= b` is `{}` then we must have `b |= a` equal to `{}`.",
+              ab, !ab).as_str());
+          self.test_equality(a, b, false,
+            format!("\n problem: if `a |= b` is `{}` then we must have `a != b`", ab).as_str());
+        }
+      }
+    }
+
+    fn test_equality<T: Eq + Debug>(&self, a: T, b: T, expected: bool, msg: &str) {
+      self.assert_expected("a == b", a == b, expected, msg);
+      self.assert_expected("b == a", b == a, expected, msg);
+    }
+
+    fn assert_expected<U: Eq + Debug>(&self, op: &str, obtained: U, expected: U, msg: &str) {
+      assert_eq!(obtained, expected,
+        "{}`{}` is equal to `{:?}` instead of the expected value `{:?}`.{}",
+          self.current_test, op, obtained, expected, msg);
+    }
+
+    fn test_strict_entail<T>(&self, a: T, b: T, expected: SKleene) where
+      T: Entailment + Debug + Eq
+    {
+      let ab = a.strict_entail(&b);
+      let ba = b.strict_entail(&a);
+      self.assert_expected("a.strict_entail(b)", ab, expected, "");
+      self.assert_expected("b.strict_entail(a)", ba, !expected, "");
+      match expected {
+        True | Unknown => self.test_equality(a, b, false,
+          format!("\n problem: if `a.strict_entail(b)` is `{}` then we must have `a != b`.", expected).as_str()),
+        False => (),
+      }
+    }
+
+    fn test_join<T>(&self, a: T, b: T, expected: T) where
+      T: BoundedJoinSemilattice + Clone + Debug + Eq
+    {
+      self.test_bot_join(a.clone());
+      let c = a.clone().join(b.clone());
+      self.test_equality(c.clone(), expected.clone(), true,
+        format!("\n problem: `a.join(b)` != {:?}.", expected).as_str());
+
+      // Test commutativity
+      let d = b.clone().join(a.clone());
+      self.test_equality(d.clone(), c.clone(), true,
+        "\n problem (commutativity): `a.join(b)` != b.join(a).");
+
+      // Test idempotence
+      let e = a.clone().join(b.clone()).join(c.clone());
+      self.test_equality(c.clone(), e.clone(), true,
+        "\n problem (idempotency): `a.join(b)` != `a.join(b).join(a.join(b))`.");
+      let f = d.clone().join(c.clone());
+      self.test_equality(c.clone(), f.clone(), true,
+        "\n problem (idempotency): `a.join(b)` != `b.join(a).join(a.join(b))`.");
+      let g = a.clone().join(a.clone());
+      let h = b.clone().join(b.clone());
+      self.test_equality(a.clone(), g.clone(), true,
+        "\n problem (idempotency): `a` != `a.join(a)`.");
+      self.test_equality(b.clone(), h.clone(), true,
+        "\n problem (idempotency): `b` != `b.join(b)`.");
+
+      // Test the in-place join.
+      let mut x = a.clone();
+      let changed = x.join_assign(b.clone());
+      self.test_equality(x, c.clone(), true,
+        "\n problem: `a.join_assign(b)` must set `a` to `a.join(b)`.");
+      self.assert_expected("a.join_assign(b)", changed, a != c,
+        "\n problem: `a.join_assign(b)` must return `true` if and only if `a` has changed.");
+
+      // Test relation with entailment.
+      self.test_entailment(c.clone(), a.clone(), True);
+      self.test_entailment(c.clone(), b.clone(), True);
+      if a == c {
+        self.test_entailment(a.clone(), b.clone(), True);
+      }
+      if b == c {
+        self.test_entailment(b.clone(), a.clone(), True);
+      }
+    }
+
+    fn test_meet<T>(&self, a: T, b: T, expected: T) where
+      T: BoundedMeetSemilattice + Clone + Debug + Eq
+    {
+      self.test_top_meet(a.clone());
+      let c = a.clone().meet(b.clone());
+      self.test_equality(c.clone(), expected.clone(), true,
+        format!("\n problem: `a.meet(b)` != {:?}.", expected).as_str());
+
+      // Test commutativity
+      let d = b.clone().meet(a.clone());
+      self.test_equality(d.clone(), c.clone(), true,
+        "\n problem (commutativity): `a.meet(b)` != b.meet(a).");
+
+      // Test idempotence
+      let e = a.clone().meet(b.clone()).meet(c.clone());
+      self.test_equality(c.clone(), e.clone(), true,
+        "\n problem (idempotency): `a.meet(b)` != `a.meet(b).meet(a.meet(b))`.");
+      let f = d.clone().meet(c.clone());
+      self.test_equality(c.clone(), f.clone(), true,
+        "\n problem (idempotency): `a.meet(b)` != `b.meet(a).meet(a.meet(b))`.");
+      let g = a.clone().meet(a.clone());
+      let h = b.clone().meet(b.clone());
+      self.test_equality(a.clone(), g.clone(), true,
+        "\n problem (idempotency): `a` != `a.meet(a)`.");
+      self.test_equality(b.clone(), h.clone(), true,
+        "\n problem (idempotency): `b` != `b.meet(b)`.");
+
+      // Test the in-place meet.
+      let mut x = a.clone();
+      let changed = x.meet_assign(b.clone());
+      self.test_equality(x, c.clone(), true,
+        "\n problem: `a.meet_assign(b)` must set `a` to `a.meet(b)`.");
+      self.assert_expected("a.meet_assign(b)", changed, a != c,
+        "\n problem: `a.meet_assign(b)` must return `true` if and only if `a` has changed.");
+
+      // Test relation with entailment.
+      self.test_entailment(a.clone(), c.clone(), True);
+      self.test_entailment(b.clone(), c.clone(), True);
+      if a == c {
+        self.test_entailment(b.clone(), a.clone(), True);
+      }
+      if b == c {
+        self.test_entailment(a.clone(), b.clone(), True);
+      }
+    }
+
+    fn test_join_associativity<T>(&self, a: T, b: T, c: T) where
+      T: Join + Clone + Debug + Eq
+    {
+      let left = a.clone().join(b.clone()).join(c.clone());
+      let right = a.join(b.join(c));
+      self.test_equality(left, right, true,
+        "\n problem (associativity): `a.join(b).join(c)` != `a.join(b.join(c))`.");
+    }
+
+    fn test_meet_associativity<T>(&self, a: T, b: T, c: T) where
+      T: Meet + Clone + Debug + Eq
+    {
+      let left = a.clone().meet(b.clone()).meet(c.clone());
+      let right = a.meet(b.meet(c));
+      self.test_equality(left, right, true,
+        "\n problem (associativity): `a.meet(b).meet(c)` != `a.meet(b.meet(c))`.");
+    }
+
+    fn test_bot_join<T>(&self, a: T) where
+      T: Join + Bot + Clone + Debug + Eq
+    {
+      let bot = T::bot();
+      self.assert_expected("a.join(bot)", a.clone().join(bot.clone()), a.clone(),
+        "\n problem: `a.join(bot)` must be equal to `a`.");
+      self.assert_expected("bot.join(a)", bot.join(a.clone()), a,
+        "\n problem: `bot.join(a)` must be equal to `a`.");
+    }
+
+    fn test_top_join<T>(&self, a: T) where
+      T: Join + Top + Clone + Debug + Eq
+    {
+      let top = T::top();
+      self.assert_expected("a.join(top)", a.clone().join(top.clone()), top.clone(),
+        "\n problem: `a.join(top)` must be equal to `top`.");
+      self.assert_expected("top.join(a)", top.clone().join(a), top,
+        "\n problem: `top.join(a)` must be equal to `top`.");
+    }
+
+    fn test_top_meet<T>(&self, a: T) where
+      T: Meet + Top + Clone + Debug + Eq
+    {
+      let top = T::top();
+      self.assert_expected("a.meet(top)", a.clone().meet(top.clone()), a.clone(),
+        "\n problem: `a.meet(top)` must be equal to `a`.");
+      self.assert_expected("top.meet(a)", top.meet(a.clone()), a,
+        "\n problem: `top.meet(a)` must be equal to `a`.");
+    }
+
+    fn test_bot_meet<T>(&self, a: T) where
+      T: Meet + Bot + Clone + Debug + Eq
+    {
+      let bot = T::bot();
+      self.assert_expected("a.meet(bot)", a.clone().meet(bot.clone()), bot.clone(),
+        "\n problem: `a.meet(bot)` must be equal to `bot`.");
+      self.assert_expected("bot.meet(a)", bot.clone().meet(a), bot,
+        "\n problem: `bot.meet(a)` must be equal to `bot`.");
+    }
+  }
+
+  /// Checks the postconditions of the widening and narrowing operators on the pairs `(data_a[i], data_b[i])`.
+  pub struct WideningTester<T>
+  {
+    test_id: usize,
+    data_a: Vec<T>,
+    data_b: Vec<T>
+  }
+
+  impl<T> WideningTester<T> where
+    T: Lattice + Widen + Narrow + Clone + Debug + Eq
+  {
+    pub fn new(test_id: usize, data_a: Vec<T>, data_b: Vec<T>) -> Self {
+      assert_eq!(data_a.len(), data_b.len());
+      WideningTester {
+        test_id: test_id,
+        data_a: data_a,
+        data_b: data_b
+      }
+    }
+
+    pub fn test_all(self) {
+      for (a, b) in self.data_a.iter().zip(self.data_b.iter()) {
+        self.test_widen(a.clone(), b.clone());
+        self.test_widen(b.clone(), a.clone());
+        self.test_narrow(a.clone(), b.clone());
+        self.test_narrow(b.clone(), a.clone());
+      }
+    }
+
+    /// `a` and `b` must both be below `a.widen(b)`.
+    pub fn test_widen(&self, a: T, b: T) {
+      let w = a.clone().widen(b.clone());
+      assert!(w.entail(&a) == True && w.entail(&b) == True,
+        "[{}] `widen`-test has failed.\n a = {:?}\n b = {:?}\n a.widen(b) = {:?}\n\
+         problem: `a.widen(b)` must entail `a` and `b`.", self.test_id, a, b, w);
+    }
+
+    /// If `b` is below `a`, then `a.narrow(b)` must be between `b` and `a`.
+    pub fn test_narrow(&self, a: T, b: T) {
+      if a.entail(&b) == True {
+        let n = a.clone().narrow(b.clone());
+        assert!(a.entail(&n) == True && n.entail(&b) == True,
+          "[{}] `narrow`-test has failed.\n a = {:?}\n b = {:?}\n a.narrow(b) = {:?}\n\
+           problem: if `a |= b` then `a |= a.narrow(b)` and `a.narrow(b) |= b` must hold.", self.test_id, a, b, n);
+      }
+    }
+
+    /// The increasing chain `y_i` is built by joining the elements `chain(0), ..., chain(i)`, for `i < length`.
+    /// The widened sequence `x_0 = y_0`, `x_i+1 = x_i.widen(y_i+1)` must be increasing, and stabilise within `steps` steps: `x_i = x_steps` for every `i >= steps`.
+    pub fn test_chain<F>(&self, length: usize, steps: usize, mut chain: F) where
+     F: FnMut(usize) -> T
+    {
+      assert!(steps < length, "[{}] the chain must be longer than the number of steps to observe the stabilisation.", self.test_id);
+      let mut y = chain(0);
+      let mut x = y.clone();
+      let mut last_change = 0;
+      for i in 1..length {
+        y = y.join(chain(i));
+        let next = x.clone().widen(y.clone());
+        self.test_widen(x.clone(), y.clone());
+        if next != x {
+          last_change = i;
+        }
+        x = next;
+      }
+      assert!(last_change <= steps,
+        "[{}] `widen`-chain test has failed.\n the widened sequence is still changing at the index {} of a chain of length {}, \
+         instead of stabilising within {} steps.\n last value = {:?}", self.test_id, last_change, length, steps, x);
+    }
+  }
+}
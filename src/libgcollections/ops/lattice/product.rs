@@ -0,0 +1,209 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Product lattices: tuples (up to arity 12) and arrays of lattices are lattices where every operation is applied component-wise.
+
+use ops::lattice::*;
+use trilean::SKleene::*;
+
+/// `ab` is the Kleene conjunction of the entailment of every component.
+/// If it is not `True`, then `a` is strictly below `b` only if `b |= a` holds for every component (computed by `ba`), otherwise `a` and `b` are unordered.
+pub fn product_entailment<F>(ab: SKleene, ba: F) -> SKleene where
+ F: FnOnce() -> SKleene
+{
+  match ab {
+    True => True,
+    _ if ba() == True => False,
+    _ => Unknown
+  }
+}
+
+macro_rules! tuple_lattice_impl
+{
+  ( $( ( $( $T:ident, $idx:tt );+ ) );* ) =>
+  {$(
+    impl<$($T: Join),+> Join for ($($T,)+)
+    {
+      fn join(self, other: Self) -> Self {
+        ($(self.$idx.join(other.$idx),)+)
+      }
+    }
+
+    impl<$($T: Meet),+> Meet for ($($T,)+)
+    {
+      fn meet(self, other: Self) -> Self {
+        ($(self.$idx.meet(other.$idx),)+)
+      }
+    }
+
+    impl<$($T: Entailment),+> Entailment for ($($T,)+)
+    {
+      fn entail(&self, other: &Self) -> SKleene {
+        let ab = True $(.and(self.$idx.entail(&other.$idx)))+;
+        product_entailment(ab, || True $(.and(other.$idx.entail(&self.$idx)))+)
+      }
+    }
+
+    impl<$($T: Widen),+> Widen for ($($T,)+)
+    {
+      fn widen(self, other: Self) -> Self {
+        ($(self.$idx.widen(other.$idx),)+)
+      }
+    }
+
+    impl<$($T: Narrow),+> Narrow for ($($T,)+)
+    {
+      fn narrow(self, other: Self) -> Self {
+        ($(self.$idx.narrow(other.$idx),)+)
+      }
+    }
+
+    impl<$($T: Top),+> Top for ($($T,)+)
+    {
+      fn top() -> Self {
+        ($($T::top(),)+)
+      }
+    }
+
+    impl<$($T: Bot),+> Bot for ($($T,)+)
+    {
+      fn bot() -> Self {
+        ($($T::bot(),)+)
+      }
+    }
+  )*}
+}
+
+tuple_lattice_impl! {
+  (A, 0);
+  (A, 0; B, 1);
+  (A, 0; B, 1; C, 2);
+  (A, 0; B, 1; C, 2; D, 3);
+  (A, 0; B, 1; C, 2; D, 3; E, 4);
+  (A, 0; B, 1; C, 2; D, 3; E, 4; F, 5);
+  (A, 0; B, 1; C, 2; D, 3; E, 4; F, 5; G, 6);
+  (A, 0; B, 1; C, 2; D, 3; E, 4; F, 5; G, 6; H, 7);
+  (A, 0; B, 1; C, 2; D, 3; E, 4; F, 5; G, 6; H, 7; I, 8);
+  (A, 0; B, 1; C, 2; D, 3; E, 4; F, 5; G, 6; H, 7; I, 8; J, 9);
+  (A, 0; B, 1; C, 2; D, 3; E, 4; F, 5; G, 6; H, 7; I, 8; J, 9; K, 10);
+  (A, 0; B, 1; C, 2; D, 3; E, 4; F, 5; G, 6; H, 7; I, 8; J, 9; K, 10; L, 11)
+}
+
+impl<L: Join, const N: usize> Join for [L; N]
+{
+  fn join(self, other: [L; N]) -> [L; N] {
+    let mut other = IntoIterator::into_iter(other);
+    self.map(|x| x.join(other.next().unwrap()))
+  }
+}
+
+impl<L: Meet, const N: usize> Meet for [L; N]
+{
+  fn meet(self, other: [L; N]) -> [L; N] {
+    let mut other = IntoIterator::into_iter(other);
+    self.map(|x| x.meet(other.next().unwrap()))
+  }
+}
+
+impl<L: Widen, const N: usize> Widen for [L; N]
+{
+  fn widen(self, other: [L; N]) -> [L; N] {
+    let mut other = IntoIterator::into_iter(other);
+    self.map(|x| x.widen(other.next().unwrap()))
+  }
+}
+
+impl<L: Narrow, const N: usize> Narrow for [L; N]
+{
+  fn narrow(self, other: [L; N]) -> [L; N] {
+    let mut other = IntoIterator::into_iter(other);
+    self.map(|x| x.narrow(other.next().unwrap()))
+  }
+}
+
+impl<L: Entailment, const N: usize> Entailment for [L; N]
+{
+  fn entail(&self, other: &[L; N]) -> SKleene {
+    let ab = self.iter().zip(other.iter())
+      .fold(True, |acc, (a, b)| acc.and(a.entail(b)));
+    product_entailment(ab, || other.iter().zip(self.iter())
+      .fold(True, |acc, (b, a)| acc.and(b.entail(a))))
+  }
+}
+
+impl<L: Top, const N: usize> Top for [L; N]
+{
+  fn top() -> [L; N] {
+    ::std::array::from_fn(|_| L::top())
+  }
+}
+
+impl<L: Bot, const N: usize> Bot for [L; N]
+{
+  fn bot() -> [L; N] {
+    ::std::array::from_fn(|_| L::bot())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ops::lattice::test::*;
+  use wrappers::optional::*;
+  use ops::lattice::combinators::Flat::Bottom as Unk;
+  use ops::lattice::combinators::Flat::Topmost as Conflict;
+  use ops::constructor::*;
+
+  fn known(x: i32) -> FlatOptional<i32> {
+    Flat::Value(Optional::singleton(x))
+  }
+
+  #[test]
+  fn pair_test() {
+    let data_a =          vec![(Unk, Unk),  (known(1), Unk), (known(1), known(2)), (known(1), Unk), (known(1), known(2)), (Conflict, Unk)];
+    let data_b =          vec![(Unk, Unk),  (Unk, known(2)), (known(1), Unk),      (known(1), Unk), (known(2), known(2)), (known(1), known(1))];
+    let expected_entail = vec![True,        Unknown,         True,                 True,            Unknown,              Unknown];
+    let expected_join =   vec![(Unk, Unk),  (known(1), known(2)), (known(1), known(2)), (known(1), Unk), (Conflict, known(2)), (Conflict, known(1))];
+    let expected_meet =   vec![(Unk, Unk),  (Unk, Unk),      (known(1), Unk),      (known(1), Unk), (Unk, known(2)),      (known(1), Unk)];
+    LatticeTester::new(0, data_a, data_b, expected_entail, expected_join, expected_meet).modular().test_all();
+  }
+
+  #[test]
+  fn triple_test() {
+    let data_a =          vec![(known(1), Unk, Unk),      (Unk, Unk, Unk),           (known(1), known(2), Conflict)];
+    let data_b =          vec![(known(1), known(2), Unk), (known(1), known(2), known(3)), (known(1), known(2), known(3))];
+    let expected_entail = vec![False,                     False,                     True];
+    let expected_join =   vec![(known(1), known(2), Unk), (known(1), known(2), known(3)), (known(1), known(2), Conflict)];
+    let expected_meet =   vec![(known(1), Unk, Unk),      (Unk, Unk, Unk),           (known(1), known(2), known(3))];
+    LatticeTester::new(1, data_a, data_b, expected_entail, expected_join, expected_meet).test_all();
+  }
+
+  #[test]
+  fn array_test() {
+    let data_a =          vec![[Unk, Unk],      [known(1), Unk], [known(1), known(2)], [Conflict, known(2)]];
+    let data_b =          vec![[Unk, known(1)], [Unk, known(1)], [known(1), known(3)], [known(1), known(2)]];
+    let expected_entail = vec![False,           Unknown,         Unknown,              True];
+    let expected_join =   vec![[Unk, known(1)], [known(1), known(1)], [known(1), Conflict], [Conflict, known(2)]];
+    let expected_meet =   vec![[Unk, Unk],      [Unk, Unk],      [known(1), Unk],      [known(1), known(2)]];
+    LatticeTester::new(2, data_a, data_b, expected_entail, expected_join, expected_meet).test_all();
+  }
+
+  #[test]
+  fn twelve_tuple_test() {
+    type Twelve = (FlatOptional<i32>, FlatOptional<i32>, FlatOptional<i32>, FlatOptional<i32>,
+      FlatOptional<i32>, FlatOptional<i32>, FlatOptional<i32>, FlatOptional<i32>,
+      FlatOptional<i32>, FlatOptional<i32>, FlatOptional<i32>, FlatOptional<i32>);
+    let bot = Twelve::bot();
+    let mut a = bot;
+    a.11 = known(11);
+    assert_eq!(a.entail(&bot), True);
+    assert_eq!(bot.entail(&a), False);
+    assert_eq!(a.join(Twelve::top()), Twelve::top());
+    assert_eq!(a.meet(bot), bot);
+  }
+}
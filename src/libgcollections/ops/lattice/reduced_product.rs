@@ -0,0 +1,248 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reduced product of two lattices: the components are ordered component-wise, and the information of each component is used to tighten the other one.
+//! The reduction is given by the type `R` implementing `Reduce<A, B>`, it is applied when an element is created and after every join, meet, widening and narrowing.
+//! If one of the components is `bot` after the reduction, the whole product collapses to `bot`.
+//!
+//! Note that applying the reduction after a widening can compromise its termination, the reduction should not go back to values that the widening has discarded.
+
+use ops::lattice::*;
+use ops::lattice::product::product_entailment;
+use trilean::SKleene::*;
+use std::marker::PhantomData;
+use std::fmt::{Formatter, Debug, Error};
+
+pub trait Reduce<A, B>
+{
+  /// Returns components below (or equal to) `a` and `b` which represent the same information as the pair `(a, b)`.
+  fn reduce(a: A, b: B) -> (A, B);
+}
+
+/// No reduction, `ReducedProduct<A, B, NoReduction>` is the product `(A, B)` where `bot` is propagated to both components.
+pub struct NoReduction;
+
+impl<A, B> Reduce<A, B> for NoReduction
+{
+  fn reduce(a: A, b: B) -> (A, B) {
+    (a, b)
+  }
+}
+
+pub struct ReducedProduct<A, B, R>
+{
+  first: A,
+  second: B,
+  phantom_reduce: PhantomData<R>
+}
+
+impl<A, B, R> ReducedProduct<A, B, R> where
+ A: Entailment + Bot,
+ B: Entailment + Bot,
+ R: Reduce<A, B>
+{
+  /// Creates the reduced element of `(first, second)`.
+  pub fn new(first: A, second: B) -> Self {
+    let (first, second) = R::reduce(first, second);
+    if A::bot().entail(&first) == True || B::bot().entail(&second) == True {
+      Self::bot()
+    }
+    else {
+      ReducedProduct {
+        first: first,
+        second: second,
+        phantom_reduce: PhantomData
+      }
+    }
+  }
+}
+
+impl<A, B, R> ReducedProduct<A, B, R>
+{
+  pub fn first(&self) -> &A {
+    &self.first
+  }
+
+  pub fn second(&self) -> &B {
+    &self.second
+  }
+
+  pub fn unwrap(self) -> (A, B) {
+    (self.first, self.second)
+  }
+}
+
+impl<A: Clone, B: Clone, R> Clone for ReducedProduct<A, B, R>
+{
+  fn clone(&self) -> Self {
+    ReducedProduct {
+      first: self.first.clone(),
+      second: self.second.clone(),
+      phantom_reduce: PhantomData
+    }
+  }
+}
+
+impl<A: PartialEq, B: PartialEq, R> PartialEq for ReducedProduct<A, B, R>
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.first == other.first && self.second == other.second
+  }
+}
+
+impl<A: Eq, B: Eq, R> Eq for ReducedProduct<A, B, R> {}
+
+impl<A: Debug, B: Debug, R> Debug for ReducedProduct<A, B, R>
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    formatter.debug_tuple("ReducedProduct")
+      .field(&self.first)
+      .field(&self.second)
+      .finish()
+  }
+}
+
+impl<A, B, R> Join for ReducedProduct<A, B, R> where
+ A: Join + Entailment + Bot,
+ B: Join + Entailment + Bot,
+ R: Reduce<A, B>
+{
+  fn join(self, other: Self) -> Self {
+    ReducedProduct::new(self.first.join(other.first), self.second.join(other.second))
+  }
+}
+
+impl<A, B, R> Meet for ReducedProduct<A, B, R> where
+ A: Meet + Entailment + Bot,
+ B: Meet + Entailment + Bot,
+ R: Reduce<A, B>
+{
+  fn meet(self, other: Self) -> Self {
+    ReducedProduct::new(self.first.meet(other.first), self.second.meet(other.second))
+  }
+}
+
+impl<A, B, R> Entailment for ReducedProduct<A, B, R> where
+ A: Entailment,
+ B: Entailment
+{
+  fn entail(&self, other: &Self) -> SKleene {
+    let ab = self.first.entail(&other.first).and(self.second.entail(&other.second));
+    product_entailment(ab, || other.first.entail(&self.first).and(other.second.entail(&self.second)))
+  }
+}
+
+impl<A, B, R> Widen for ReducedProduct<A, B, R> where
+ A: Widen + Entailment + Bot,
+ B: Widen + Entailment + Bot,
+ R: Reduce<A, B>
+{
+  fn widen(self, other: Self) -> Self {
+    ReducedProduct::new(self.first.widen(other.first), self.second.widen(other.second))
+  }
+}
+
+impl<A, B, R> Narrow for ReducedProduct<A, B, R> where
+ A: Narrow + Entailment + Bot,
+ B: Narrow + Entailment + Bot,
+ R: Reduce<A, B>
+{
+  fn narrow(self, other: Self) -> Self {
+    ReducedProduct::new(self.first.narrow(other.first), self.second.narrow(other.second))
+  }
+}
+
+/// `top` is not reduced since it must be above every element.
+impl<A: Top, B: Top, R> Top for ReducedProduct<A, B, R>
+{
+  fn top() -> Self {
+    ReducedProduct {
+      first: A::top(),
+      second: B::top(),
+      phantom_reduce: PhantomData
+    }
+  }
+}
+
+impl<A: Bot, B: Bot, R> Bot for ReducedProduct<A, B, R>
+{
+  fn bot() -> Self {
+    ReducedProduct {
+      first: A::bot(),
+      second: B::bot(),
+      phantom_reduce: PhantomData
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ops::lattice::test::*;
+  use ops::lattice::combinators::Flat::*;
+
+  /// Product of the constant and parity domains, where `Topmost` represents any value.
+  /// The reduction deduces the parity of a constant, and detects an inconsistency between a constant and a parity.
+  struct Parity;
+
+  type ConstParity = ReducedProduct<Flat<i32>, Flat<bool>, Parity>;
+
+  impl Reduce<Flat<i32>, Flat<bool>> for Parity
+  {
+    fn reduce(constant: Flat<i32>, even: Flat<bool>) -> (Flat<i32>, Flat<bool>) {
+      match (constant, even) {
+        (Value(n), Topmost) => (Value(n), Value(n % 2 == 0)),
+        (Value(n), Value(even)) if (n % 2 == 0) != even => (Bottom, Bottom),
+        (_, Bottom) => (Bottom, Bottom),
+        x => x
+      }
+    }
+  }
+
+  fn cp(constant: Flat<i32>, even: Flat<bool>) -> ConstParity {
+    ConstParity::new(constant, even)
+  }
+
+  #[test]
+  fn reduction_test() {
+    assert_eq!(cp(Value(2), Topmost).unwrap(), (Value(2), Value(true)));
+    assert_eq!(cp(Value(3), Value(true)), ConstParity::bot());
+    assert_eq!(cp(Bottom, Value(true)), ConstParity::bot());
+    assert_eq!(cp(Topmost, Value(false)).unwrap(), (Topmost, Value(false)));
+  }
+
+  #[test]
+  fn lattice_test() {
+    let two = cp(Value(2), Topmost);
+    let four = cp(Value(4), Topmost);
+    let three = cp(Value(3), Topmost);
+    let even = cp(Topmost, Value(true));
+    let odd = cp(Topmost, Value(false));
+    let bot = ConstParity::bot();
+    let top = ConstParity::top();
+    let data_a =          vec![two.clone(), two.clone(), two.clone(), two.clone(), even.clone(), bot.clone()];
+    let data_b =          vec![four.clone(), three.clone(), even.clone(), odd.clone(), odd.clone(), three.clone()];
+    let expected_entail = vec![Unknown, Unknown, False, Unknown, Unknown, False];
+    let expected_join =   vec![even.clone(), top.clone(), even.clone(), top.clone(), top.clone(), three.clone()];
+    let expected_meet =   vec![bot.clone(), bot.clone(), two.clone(), bot.clone(), bot.clone(), bot.clone()];
+    LatticeTester::new(0, data_a, data_b, expected_entail, expected_join, expected_meet).test_all();
+
+    // Without reduction, `(2, odd)` is not collapsed and the meet of `2` and `odd` is not `bot`.
+    let p = ReducedProduct::<Flat<i32>, Flat<bool>, NoReduction>::new;
+    assert_eq!(p(Value(2), Topmost).meet(p(Topmost, Value(false))).unwrap(), (Value(2), Value(false)));
+    assert_eq!(p(Value(2), Bottom), ReducedProduct::bot());
+  }
+
+  #[test]
+  fn widening_test() {
+    let tester = WideningTester::new(0, vec![cp(Value(1), Topmost), cp(Topmost, Value(true))],
+      vec![cp(Value(3), Topmost), cp(Value(2), Topmost)]);
+    tester.test_chain(10, 1, |i| cp(Value(i as i32), Topmost));
+    tester.test_all();
+  }
+}
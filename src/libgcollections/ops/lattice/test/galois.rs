@@ -0,0 +1,174 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::*;
+use std::marker::PhantomData;
+
+/// Checks that `G` is a Galois connection on the sampled concrete and abstract elements.
+/// The adjunction is checked on every pair `(concrete[i], abstract[j])`, the monotonicity of the abstraction on every pair of concrete elements and the monotonicity of the concretization on every pair of abstract elements.
+pub struct GaloisTester<C, A, G>
+{
+  test_id: usize,
+  concrete_data: Vec<C>,
+  abstract_data: Vec<A>,
+  phantom_connection: PhantomData<G>
+}
+
+impl<C, A, G> GaloisTester<C, A, G> where
+  C: Entailment + Debug,
+  A: Entailment + Debug,
+  G: GaloisConnection<C, A>
+{
+  pub fn new(test_id: usize, concrete_data: Vec<C>, abstract_data: Vec<A>) -> Self {
+    GaloisTester {
+      test_id: test_id,
+      concrete_data: concrete_data,
+      abstract_data: abstract_data,
+      phantom_connection: PhantomData
+    }
+  }
+
+  pub fn test_all(self) {
+    for c in &self.concrete_data {
+      for a in &self.abstract_data {
+        self.test_adjunction(c, a);
+      }
+    }
+    for c1 in &self.concrete_data {
+      for c2 in &self.concrete_data {
+        self.test_abstraction_monotonicity(c1, c2);
+      }
+    }
+    for a1 in &self.abstract_data {
+      for a2 in &self.abstract_data {
+        self.test_concretization_monotonicity(a1, a2);
+      }
+    }
+  }
+
+  /// `α(c) ⊑ a` if and only if `c ⊑ γ(a)`.
+  pub fn test_adjunction(&self, c: &C, a: &A) {
+    let alpha_c = G::abstraction(c);
+    let gamma_a = G::concretization(a);
+    let abstract_below = a.entail(&alpha_c) == True;
+    let concrete_below = gamma_a.entail(c) == True;
+    assert!(abstract_below == concrete_below,
+      "[{}] `adjunction`-test has failed.\n c = {:?}\n a = {:?}\n α(c) = {:?}\n γ(a) = {:?}\n\
+       problem: `α(c) ⊑ a` is `{}` but `c ⊑ γ(a)` is `{}`.",
+      self.test_id, c, a, alpha_c, gamma_a, abstract_below, concrete_below);
+  }
+
+  /// If `c1 ⊑ c2` then `α(c1) ⊑ α(c2)`.
+  pub fn test_abstraction_monotonicity(&self, c1: &C, c2: &C) {
+    if c2.entail(c1) == True {
+      let alpha_c1 = G::abstraction(c1);
+      let alpha_c2 = G::abstraction(c2);
+      assert!(alpha_c2.entail(&alpha_c1) == True,
+        "[{}] `abstraction monotonicity`-test has failed.\n c1 = {:?}\n c2 = {:?}\n α(c1) = {:?}\n α(c2) = {:?}\n\
+         problem: `c1 ⊑ c2` but not `α(c1) ⊑ α(c2)`.",
+        self.test_id, c1, c2, alpha_c1, alpha_c2);
+    }
+  }
+
+  /// If `a1 ⊑ a2` then `γ(a1) ⊑ γ(a2)`.
+  pub fn test_concretization_monotonicity(&self, a1: &A, a2: &A) {
+    if a2.entail(a1) == True {
+      let gamma_a1 = G::concretization(a1);
+      let gamma_a2 = G::concretization(a2);
+      assert!(gamma_a2.entail(&gamma_a1) == True,
+        "[{}] `concretization monotonicity`-test has failed.\n a1 = {:?}\n a2 = {:?}\n γ(a1) = {:?}\n γ(a2) = {:?}\n\
+         problem: `a1 ⊑ a2` but not `γ(a1) ⊑ γ(a2)`.",
+        self.test_id, a1, a2, gamma_a1, gamma_a2);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use wrappers::btree_set::BTreeSet;
+  use wrappers::order::Max;
+  use ops::lattice::combinators::Flat::*;
+
+  fn set(values: &[u8]) -> BTreeSet<u8> {
+    BTreeSet::wrap(values.iter().cloned().collect())
+  }
+
+  fn concrete_data() -> Vec<BTreeSet<u8>> {
+    vec![set(&[]), set(&[0]), set(&[1]), set(&[0, 1]), set(&[2, 3]), set(&[255]), set(&[0, 3, 255])]
+  }
+
+  /// Abstracts a set of values by its constant value, if any.
+  struct Constant;
+
+  impl GaloisConnection<BTreeSet<u8>, Flat<u8>> for Constant
+  {
+    fn abstraction(concrete: &BTreeSet<u8>) -> Flat<u8> {
+      match concrete.len() {
+        0 => Bottom,
+        1 => Value(*concrete.iter().next().unwrap()),
+        _ => Topmost
+      }
+    }
+
+    fn concretization(abstract_value: &Flat<u8>) -> BTreeSet<u8> {
+      match *abstract_value {
+        Bottom => set(&[]),
+        Value(x) => set(&[x]),
+        Topmost => BTreeSet::wrap((0..=255).collect())
+      }
+    }
+  }
+
+  /// Abstracts a set of values by its maximum, the empty set and `{0}` have the same abstraction.
+  struct UpperBound;
+
+  impl GaloisConnection<BTreeSet<u8>, Max<u8>> for UpperBound
+  {
+    fn abstraction(concrete: &BTreeSet<u8>) -> Max<u8> {
+      Max::wrap(concrete.iter().next_back().cloned().unwrap_or(0))
+    }
+
+    fn concretization(abstract_value: &Max<u8>) -> BTreeSet<u8> {
+      BTreeSet::wrap((0..=**abstract_value).collect())
+    }
+  }
+
+  /// Not a Galois connection: the abstraction of `{0, 1}` is too precise.
+  struct WrongUpperBound;
+
+  impl GaloisConnection<BTreeSet<u8>, Max<u8>> for WrongUpperBound
+  {
+    fn abstraction(concrete: &BTreeSet<u8>) -> Max<u8> {
+      Max::wrap(concrete.iter().next().cloned().unwrap_or(0))
+    }
+
+    fn concretization(abstract_value: &Max<u8>) -> BTreeSet<u8> {
+      UpperBound::concretization(abstract_value)
+    }
+  }
+
+  #[test]
+  fn constant_test() {
+    GaloisTester::<_, _, Constant>::new(0, concrete_data(),
+      vec![Bottom, Value(0), Value(1), Value(255), Topmost]).test_all();
+  }
+
+  #[test]
+  fn upper_bound_test() {
+    GaloisTester::<_, _, UpperBound>::new(1, concrete_data(),
+      vec![Max::wrap(0), Max::wrap(1), Max::wrap(2), Max::wrap(254), Max::wrap(255)]).test_all();
+  }
+
+  #[test]
+  #[should_panic(expected = "adjunction")]
+  fn wrong_upper_bound_test() {
+    GaloisTester::<_, _, WrongUpperBound>::new(2, concrete_data(),
+      vec![Max::wrap(0), Max::wrap(1), Max::wrap(2)]).test_all();
+  }
+}
@@ -0,0 +1,430 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Property-based testing of the lattice laws: `RandomLatticeTester` checks the laws of `LatticeTester` on randomly generated triples.
+//! The elements are generated either by a closure or by the `Arbitrary` trait.
+//! When a triple fails, it is shrunk to a smaller failing triple and the error message contains the seed to reproduce the test.
+//!
+//! ```rust
+//! use gcollections::ops::*;
+//! use gcollections::ops::lattice::test::*;
+//!
+//! RandomLatticeTester::<Flat<i8>, _>::arbitrary(0)
+//!   .samples(500)
+//!   .modular()
+//!   .test_all();
+//! ```
+
+use super::*;
+use wrappers::optional::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::env;
+
+/// Pseudo-random number generator (SplitMix64), deterministic for a given seed.
+#[derive(Clone, Debug)]
+pub struct Rng
+{
+  state: u64
+}
+
+impl Rng
+{
+  pub fn new(seed: u64) -> Rng {
+    Rng {
+      state: seed
+    }
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+  }
+
+  /// Uniform value in `[0, n[`, `n` must be strictly positive.
+  pub fn below(&mut self, n: u64) -> u64 {
+    self.next_u64() % n
+  }
+
+  pub fn next_bool(&mut self) -> bool {
+    self.next_u64() & 1 == 1
+  }
+
+  /// Fisher-Yates shuffle.
+  pub fn shuffle<T>(&mut self, values: &mut [T]) {
+    for i in (1..values.len()).rev() {
+      let j = self.below(i as u64 + 1) as usize;
+      values.swap(i, j);
+    }
+  }
+}
+
+/// Seed taken from the clock, a test using it should print it when it fails.
+pub fn clock_seed() -> u64 {
+  SystemTime::now().duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs() ^ (d.subsec_nanos() as u64) << 32)
+    .unwrap_or(0)
+}
+
+/// Seed of a unit test: `default` unless the environment variable `GCOLLECTIONS_TEST_SEED` is set, either to a seed or to `clock` for a seed taken from the clock.
+/// The tests are thus reproducible, and a longer random exploration is opt-in.
+pub fn test_seed(default: u64) -> u64 {
+  match env::var("GCOLLECTIONS_TEST_SEED") {
+    Ok(ref value) if value == "clock" => clock_seed(),
+    Ok(value) => value.parse().expect("`GCOLLECTIONS_TEST_SEED` must be `clock` or an unsigned integer."),
+    Err(_) => default
+  }
+}
+
+/// Types that can be randomly generated and shrunk.
+pub trait Arbitrary: Sized
+{
+  fn arbitrary(rng: &mut Rng) -> Self;
+
+  /// Values simpler than `self`, the first ones being the simplest.
+  fn shrink(&self) -> Vec<Self> {
+    vec![]
+  }
+}
+
+/// Integers are generated in a small range around zero so that equal values often occur, which matters to test lattice operations.
+macro_rules! integer_arbitrary_impl
+{
+  ( $( $t:ty, $offset:expr );* ) =>
+  {$(
+    impl Arbitrary for $t
+    {
+      fn arbitrary(rng: &mut Rng) -> $t {
+        (rng.below(17) as $t).wrapping_sub($offset)
+      }
+
+      fn shrink(&self) -> Vec<$t> {
+        let mut candidates = vec![];
+        for x in [0, *self / 2] {
+          if x != *self && !candidates.contains(&x) {
+            candidates.push(x);
+          }
+        }
+        candidates
+      }
+    }
+  )*}
+}
+
+integer_arbitrary_impl! {
+  i8, 8; i16, 8; i32, 8; i64, 8; isize, 8;
+  u8, 0; u16, 0; u32, 0; u64, 0; usize, 0
+}
+
+impl Arbitrary for bool
+{
+  fn arbitrary(rng: &mut Rng) -> bool {
+    rng.next_bool()
+  }
+
+  fn shrink(&self) -> Vec<bool> {
+    if *self { vec![false] } else { vec![] }
+  }
+}
+
+impl<T: Arbitrary> Arbitrary for Flat<T>
+{
+  fn arbitrary(rng: &mut Rng) -> Flat<T> {
+    match rng.below(4) {
+      0 => Flat::Bottom,
+      1 => Flat::Topmost,
+      _ => Flat::Value(T::arbitrary(rng))
+    }
+  }
+
+  fn shrink(&self) -> Vec<Flat<T>> {
+    match *self {
+      Flat::Bottom => vec![],
+      Flat::Topmost => vec![Flat::Bottom],
+      Flat::Value(ref x) => {
+        let mut candidates = vec![Flat::Bottom, Flat::Topmost];
+        candidates.extend(x.shrink().into_iter().map(Flat::Value));
+        candidates
+      }
+    }
+  }
+}
+
+impl<L: Arbitrary> Arbitrary for Lifted<L>
+{
+  fn arbitrary(rng: &mut Rng) -> Lifted<L> {
+    if rng.below(4) == 0 { Lifted::Bottom }
+    else { Lifted::Value(L::arbitrary(rng)) }
+  }
+
+  fn shrink(&self) -> Vec<Lifted<L>> {
+    match *self {
+      Lifted::Bottom => vec![],
+      Lifted::Value(ref x) => {
+        let mut candidates = vec![Lifted::Bottom];
+        candidates.extend(x.shrink().into_iter().map(Lifted::Value));
+        candidates
+      }
+    }
+  }
+}
+
+impl<L: Arbitrary> Arbitrary for WithTop<L>
+{
+  fn arbitrary(rng: &mut Rng) -> WithTop<L> {
+    if rng.below(4) == 0 { WithTop::Topmost }
+    else { WithTop::Value(L::arbitrary(rng)) }
+  }
+
+  fn shrink(&self) -> Vec<WithTop<L>> {
+    match *self {
+      WithTop::Topmost => vec![],
+      WithTop::Value(ref x) => {
+        let mut candidates = vec![WithTop::Topmost];
+        candidates.extend(x.shrink().into_iter().map(WithTop::Value));
+        candidates
+      }
+    }
+  }
+}
+
+impl<L: Arbitrary> Arbitrary for Dual<L>
+{
+  fn arbitrary(rng: &mut Rng) -> Dual<L> {
+    Dual::wrap(L::arbitrary(rng))
+  }
+
+  fn shrink(&self) -> Vec<Dual<L>> {
+    (**self).shrink().into_iter().map(Dual::wrap).collect()
+  }
+}
+
+impl<T: Arbitrary> Arbitrary for Optional<T>
+{
+  fn arbitrary(rng: &mut Rng) -> Optional<T> {
+    if rng.below(4) == 0 { Optional::wrap(None) }
+    else { Optional::wrap(Some(T::arbitrary(rng))) }
+  }
+
+  fn shrink(&self) -> Vec<Optional<T>> {
+    match self.as_ref() {
+      None => vec![],
+      Some(x) => {
+        let mut candidates = vec![Optional::wrap(None)];
+        candidates.extend(x.shrink().into_iter().map(|x| Optional::wrap(Some(x))));
+        candidates
+      }
+    }
+  }
+}
+
+macro_rules! tuple_arbitrary_impl
+{
+  ( $( ( $( $T:ident, $idx:tt );+ ) );* ) =>
+  {$(
+    impl<$($T: Arbitrary + Clone),+> Arbitrary for ($($T,)+)
+    {
+      fn arbitrary(rng: &mut Rng) -> Self {
+        ($($T::arbitrary(rng),)+)
+      }
+
+      fn shrink(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+        $(
+          for x in self.$idx.shrink() {
+            let mut candidate = self.clone();
+            candidate.$idx = x;
+            candidates.push(candidate);
+          }
+        )+
+        candidates
+      }
+    }
+  )*}
+}
+
+tuple_arbitrary_impl! {
+  (A, 0; B, 1);
+  (A, 0; B, 1; C, 2);
+  (A, 0; B, 1; C, 2; D, 3)
+}
+
+type Shrinker<T> = Box<dyn Fn(&T) -> Vec<T>>;
+
+/// Checks the laws of `LatticeTester` on `samples` triples generated by `generator`.
+pub struct RandomLatticeTester<T, G>
+{
+  test_id: usize,
+  seed: u64,
+  samples: usize,
+  generator: G,
+  shrinker: Shrinker<T>,
+  distributive: bool,
+  modular: bool
+}
+
+impl<T, G> RandomLatticeTester<T, G> where
+  T: BoundedLattice + Clone + Debug + Eq,
+  G: Fn(&mut Rng) -> T
+{
+  /// The seed is taken from the clock, it is printed when a test fails and can be fixed with `seed` (see `test_seed` for the unit tests).
+  pub fn new(test_id: usize, generator: G) -> Self {
+    RandomLatticeTester {
+      test_id: test_id,
+      seed: clock_seed(),
+      samples: 2000,
+      generator: generator,
+      shrinker: Box::new(|_| vec![]),
+      distributive: false,
+      modular: false
+    }
+  }
+
+  pub fn seed(mut self, seed: u64) -> Self {
+    self.seed = seed;
+    self
+  }
+
+  pub fn samples(mut self, samples: usize) -> Self {
+    self.samples = samples;
+    self
+  }
+
+  /// `shrinker(x)` returns values simpler than `x`, used to minimize a failing triple.
+  pub fn shrink_with<S>(mut self, shrinker: S) -> Self where
+   S: Fn(&T) -> Vec<T> + 'static
+  {
+    self.shrinker = Box::new(shrinker);
+    self
+  }
+
+  pub fn distributive(mut self) -> Self {
+    self.distributive = true;
+    self.modular = true;
+    self
+  }
+
+  pub fn modular(mut self) -> Self {
+    self.modular = true;
+    self
+  }
+
+  pub fn test_all(self) {
+    let mut rng = Rng::new(self.seed);
+    for sample in 0..self.samples {
+      let triple = [(self.generator)(&mut rng), (self.generator)(&mut rng), (self.generator)(&mut rng)];
+      if let Err(msg) = self.check(&triple) {
+        let (triple, msg) = self.shrink(triple, msg);
+        panic!("[{}] random lattice test has failed on the sample {} with the seed {}.\n\
+                shrunk failing data:\n a = {:?}\n b = {:?}\n c = {:?}\n\
+                failure: {}", self.test_id, sample, self.seed, triple[0], triple[1], triple[2], msg);
+      }
+    }
+  }
+
+  fn check(&self, triple: &[T; 3]) -> Result<(), String> {
+    let tester = LatticeTester::from_data(self.test_id, vec![triple[0].clone()], vec![triple[1].clone()])
+      .with_data_c(vec![triple[2].clone()]);
+    let tester = if self.distributive { tester.distributive() } else { tester };
+    let tester = if self.modular { tester.modular() } else { tester };
+    catch_unwind(AssertUnwindSafe(|| tester.test_all()))
+      .map_err(|payload|
+        payload.downcast_ref::<String>().cloned()
+          .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+          .unwrap_or_default())
+  }
+
+  /// Greedily replaces an element of the triple by a simpler one while the test still fails.
+  fn shrink(&self, mut triple: [T; 3], mut msg: String) -> ([T; 3], String) {
+    let mut steps = 0;
+    'shrinking: while steps < 1000 {
+      for i in 0..3 {
+        for candidate in (self.shrinker)(&triple[i]) {
+          steps += 1;
+          let mut smaller = triple.clone();
+          smaller[i] = candidate;
+          if let Err(m) = self.check(&smaller) {
+            triple = smaller;
+            msg = m;
+            continue 'shrinking;
+          }
+        }
+      }
+      break;
+    }
+    (triple, msg)
+  }
+}
+
+impl<T> RandomLatticeTester<T, fn(&mut Rng) -> T> where
+  T: BoundedLattice + Arbitrary + Clone + Debug + Eq + 'static
+{
+  /// Generates and shrinks the elements with the `Arbitrary` trait.
+  pub fn arbitrary(test_id: usize) -> Self {
+    RandomLatticeTester::new(test_id, T::arbitrary as fn(&mut Rng) -> T)
+      .shrink_with(T::shrink)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use trilean::SKleene;
+
+  #[test]
+  fn combinators_test() {
+    RandomLatticeTester::<Flat<i8>, _>::arbitrary(0).seed(1).modular().test_all();
+    RandomLatticeTester::<Lifted<Flat<u8>>, _>::arbitrary(1).seed(2).modular().test_all();
+    RandomLatticeTester::<Dual<WithTop<Flat<i32>>>, _>::arbitrary(2).seed(3).modular().test_all();
+    RandomLatticeTester::<(Flat<bool>, FlatOptional<u8>), _>::arbitrary(3).seed(4).test_all();
+  }
+
+  #[test]
+  fn generator_test() {
+    RandomLatticeTester::new(4, |rng: &mut Rng| Flat::Value(rng.below(3)))
+      .seed(5)
+      .samples(100)
+      .test_all();
+  }
+
+  /// A "lattice" on integers whose join is the sum, it is not idempotent.
+  #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+  struct Sum(u8);
+
+  impl Join for Sum {
+    fn join(self, other: Sum) -> Sum { Sum(self.0.max(other.0).saturating_add(self.0.min(other.0))) }
+  }
+
+  impl Meet for Sum {
+    fn meet(self, other: Sum) -> Sum { Sum(self.0.min(other.0)) }
+  }
+
+  impl Entailment for Sum {
+    fn entail(&self, other: &Sum) -> SKleene { SKleene::from_bool(self.0 >= other.0) }
+  }
+
+  impl Top for Sum {
+    fn top() -> Sum { Sum(u8::MAX) }
+  }
+
+  impl Bot for Sum {
+    fn bot() -> Sum { Sum(0) }
+  }
+
+  #[test]
+  #[should_panic(expected = "a = Sum(1)")]
+  fn shrinking_test() {
+    RandomLatticeTester::new(5, |rng: &mut Rng| Sum(rng.below(100) as u8 + 1))
+      .shrink_with(|x: &Sum| (1..x.0).map(Sum).collect())
+      .seed(6)
+      .test_all();
+  }
+}
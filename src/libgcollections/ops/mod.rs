@@ -12,6 +12,7 @@ pub mod cardinality;
 pub mod constructor;
 pub mod multiset;
 pub mod sequence;
+pub mod snapshot;
 #[macro_use]
 pub mod set;
 pub mod lattice;
@@ -22,5 +23,8 @@ pub use ops::cardinality::*;
 pub use ops::constructor::*;
 pub use ops::multiset::*;
 pub use ops::sequence::*;
+pub use ops::snapshot::*;
 pub use ops::set::*;
-pub use ops::lattice::*;
\ No newline at end of file
+pub use ops::lattice::*;
+// Both `set` and `lattice` have a `test` module, `ops::test` keeps referring to the lattice testers.
+pub use ops::lattice::test;
\ No newline at end of file
@@ -7,6 +7,9 @@
 // except according to those terms.
 
 use kind::*;
+use trilean::SKleene;
+
+pub mod test;
 
 // Basic set operations
 pub trait Intersection<RHS = Self> {
@@ -29,6 +32,7 @@ pub trait SymmetricDifference<RHS = Self> {
   fn symmetric_difference(&self, rhs: &RHS) -> Self::Output;
 }
 
+/// The complement is taken relatively to a universe, see `wrappers::powerset::Powerset`.
 pub trait Complement {
   fn complement(&self) -> Self;
 }
@@ -38,6 +42,11 @@ pub trait Contains : Collection {
   fn contains(&self, value: &Self::Item) -> bool;
 }
 
+/// Removes `value` from the collection and returns `true` if it was contained.
+pub trait Remove : Collection {
+  fn remove(&mut self, value: &Self::Item) -> bool;
+}
+
 pub trait Disjoint<RHS = Self> {
   fn is_disjoint(&self, rhs: &RHS) -> bool;
 }
@@ -54,6 +63,80 @@ pub trait Overlap<RHS = Self> {
   fn overlap(&self, rhs: &RHS) -> bool;
 }
 
+// Three-valued membership: approximate collections (such as Bloom filters or abstract domains) answer `Unknown` when they cannot decide.
+// They are implemented for every type implementing the corresponding boolean trait.
+pub trait MayContain : Collection {
+  fn may_contain(&self, value: &Self::Item) -> SKleene;
+}
+
+pub trait MayDisjoint<RHS = Self> {
+  fn may_be_disjoint(&self, rhs: &RHS) -> SKleene;
+}
+
+pub trait MaySubset<RHS = Self> {
+  fn may_be_subset(&self, rhs: &RHS) -> SKleene;
+}
+
+pub trait MayProperSubset<RHS = Self> {
+  fn may_be_proper_subset(&self, rhs: &RHS) -> SKleene;
+}
+
+pub trait MayOverlap<RHS = Self> {
+  fn may_overlap(&self, rhs: &RHS) -> SKleene;
+}
+
+// For an explanation on the macros, see `lib.rs`.
+
+macro_rules! may_contain_impl
+{
+  ( $( $keyword:tt ),*) =>
+  {
+    impl<R> MayContain for R where
+     R: Contains
+    {
+      $($keyword)* fn may_contain(&self, value: &R::Item) -> SKleene {
+        SKleene::from_bool(self.contains(value))
+      }
+    }
+  }
+}
+
+#[cfg(feature = "nightly")]
+may_contain_impl!(default);
+#[cfg(not(feature = "nightly"))]
+may_contain_impl!();
+
+macro_rules! may_predicate_impl
+{
+  ( $may_trait:ident, $may_method:ident, $bool_trait:ident, $bool_method:ident; $( $keyword:tt ),*) =>
+  {
+    impl<R, RHS> $may_trait<RHS> for R where
+     R: $bool_trait<RHS>
+    {
+      $($keyword)* fn $may_method(&self, rhs: &RHS) -> SKleene {
+        SKleene::from_bool(self.$bool_method(rhs))
+      }
+    }
+  }
+}
+
+#[cfg(feature = "nightly")]
+may_predicate_impl!(MayDisjoint, may_be_disjoint, Disjoint, is_disjoint; default);
+#[cfg(not(feature = "nightly"))]
+may_predicate_impl!(MayDisjoint, may_be_disjoint, Disjoint, is_disjoint;);
+#[cfg(feature = "nightly")]
+may_predicate_impl!(MaySubset, may_be_subset, Subset, is_subset; default);
+#[cfg(not(feature = "nightly"))]
+may_predicate_impl!(MaySubset, may_be_subset, Subset, is_subset;);
+#[cfg(feature = "nightly")]
+may_predicate_impl!(MayProperSubset, may_be_proper_subset, ProperSubset, is_proper_subset; default);
+#[cfg(not(feature = "nightly"))]
+may_predicate_impl!(MayProperSubset, may_be_proper_subset, ProperSubset, is_proper_subset;);
+#[cfg(feature = "nightly")]
+may_predicate_impl!(MayOverlap, may_overlap, Overlap, overlap; default);
+#[cfg(not(feature = "nightly"))]
+may_predicate_impl!(MayOverlap, may_overlap, Overlap, overlap;);
+
 macro_rules! contains_deref_impl {
   ($t:ty) => {
     fn contains(&self, value: &$t) -> bool {
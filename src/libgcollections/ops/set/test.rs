@@ -0,0 +1,227 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use kind::*;
+use ops::set::*;
+use trilean::SKleene::*;
+use std::fmt::Debug;
+
+/// Checks that the three-valued set predicates do not contradict each other on the sampled sets and elements.
+/// An `Unknown` answer is always consistent, but two definite answers must agree with the laws of sets: for example, if `a` contains `x` and `b` does not, `a` cannot be a subset of `b`.
+pub struct MaySetTester<S: Collection>
+{
+  test_id: usize,
+  sets: Vec<S>,
+  elements: Vec<S::Item>
+}
+
+impl<S> MaySetTester<S> where
+  S: MayContain + MayDisjoint + MaySubset + MayProperSubset + MayOverlap + Debug,
+  S::Item: Debug
+{
+  pub fn new(test_id: usize, sets: Vec<S>, elements: Vec<S::Item>) -> Self {
+    MaySetTester {
+      test_id: test_id,
+      sets: sets,
+      elements: elements
+    }
+  }
+
+  pub fn test_all(self) {
+    for a in &self.sets {
+      self.test_reflexivity(a);
+      for b in &self.sets {
+        self.test_overlap(a, b);
+        self.test_subset(a, b);
+        for x in &self.elements {
+          self.test_witness(a, b, x);
+        }
+      }
+    }
+  }
+
+  fn fail(&self, test: &str, a: &S, b: &S, problem: String) -> ! {
+    panic!("[{}] `{}`-test has failed.\n a = {:?}\n b = {:?}\n problem: {}",
+      self.test_id, test, a, b, problem);
+  }
+
+  /// `a ⊆ a` cannot be `False` and `a ⊂ a` cannot be `True`.
+  pub fn test_reflexivity(&self, a: &S) {
+    let subset = a.may_be_subset(a);
+    let proper = a.may_be_proper_subset(a);
+    if subset == False || proper == True {
+      self.fail("reflexivity", a, a,
+        format!("`a ⊆ a` is `{:?}` and `a ⊂ a` is `{:?}`.", subset, proper));
+    }
+  }
+
+  /// The overlap and disjointness are symmetric, and `disjoint(a, b)` is the negation of `overlap(a, b)`.
+  pub fn test_overlap(&self, a: &S, b: &S) {
+    let disjoint = a.may_be_disjoint(b);
+    let overlap = a.may_overlap(b);
+    if disjoint != !overlap {
+      self.fail("disjoint-overlap", a, b,
+        format!("`disjoint(a, b)` is `{:?}` but `overlap(a, b)` is `{:?}`.", disjoint, overlap));
+    }
+    if disjoint != b.may_be_disjoint(a) || overlap != b.may_overlap(a) {
+      self.fail("symmetry", a, b,
+        format!("`disjoint(a, b)` is `{:?}` and `overlap(a, b)` is `{:?}`, but `disjoint(b, a)` is `{:?}` and `overlap(b, a)` is `{:?}`.",
+          disjoint, overlap, b.may_be_disjoint(a), b.may_overlap(a)));
+    }
+  }
+
+  /// `a ⊂ b` implies `a ⊆ b` and `b ⊈ a`.
+  pub fn test_subset(&self, a: &S, b: &S) {
+    let subset = a.may_be_subset(b);
+    let proper = a.may_be_proper_subset(b);
+    let reverse = b.may_be_subset(a);
+    if proper == True && (subset == False || reverse == True) {
+      self.fail("proper subset", a, b,
+        format!("`a ⊂ b` is `True` but `a ⊆ b` is `{:?}` and `b ⊆ a` is `{:?}`.", subset, reverse));
+    }
+    if subset == False && proper != False {
+      self.fail("proper subset", a, b,
+        format!("`a ⊆ b` is `False` but `a ⊂ b` is `{:?}`.", proper));
+    }
+  }
+
+  /// The membership of `x` must agree with the subset and overlap answers:
+  /// * if `a ⊆ b` and `x ∈ a`, then `x ∈ b`,
+  /// * if `x ∈ a` and `x ∈ b`, then `a` and `b` overlap.
+  pub fn test_witness(&self, a: &S, b: &S, x: &S::Item) {
+    let in_a = a.may_contain(x);
+    let in_b = b.may_contain(x);
+    if a.may_be_subset(b) == True && in_a == True && in_b == False {
+      self.fail("subset witness", a, b,
+        format!("`a ⊆ b` is `True` but `{:?}` is in `a` and not in `b`.", x));
+    }
+    if in_a == True && in_b == True && a.may_overlap(b) == False {
+      self.fail("overlap witness", a, b,
+        format!("`{:?}` is in `a` and `b` but `overlap(a, b)` is `False`.", x));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use wrappers::btree_set::BTreeSet;
+  use trilean::SKleene;
+
+  fn set(values: &[u32]) -> BTreeSet<u32> {
+    BTreeSet::wrap(values.iter().cloned().collect())
+  }
+
+  /// Approximation of a set of integers which only remembers the values modulo 8, as a Bloom filter with a single hash function.
+  /// Membership is `False` when the bucket of a value is empty and `Unknown` otherwise.
+  #[derive(Debug)]
+  struct Buckets
+  {
+    bits: u8
+  }
+
+  impl Buckets
+  {
+    fn new(values: &[u32]) -> Buckets {
+      Buckets { bits: values.iter().fold(0, |bits, x| bits | (1 << (x % 8))) }
+    }
+  }
+
+  impl Collection for Buckets
+  {
+    type Item = u32;
+  }
+
+  impl MayContain for Buckets
+  {
+    fn may_contain(&self, value: &u32) -> SKleene {
+      if self.bits & (1 << (value % 8)) == 0 { False } else { Unknown }
+    }
+  }
+
+  impl MayOverlap for Buckets
+  {
+    fn may_overlap(&self, other: &Buckets) -> SKleene {
+      if self.bits & other.bits == 0 { False } else { Unknown }
+    }
+  }
+
+  impl MayDisjoint for Buckets
+  {
+    fn may_be_disjoint(&self, other: &Buckets) -> SKleene {
+      !self.may_overlap(other)
+    }
+  }
+
+  /// A non-empty bucket holds at least one value, so a bucket of `self` missing in `other` refutes the inclusion.
+  impl MaySubset for Buckets
+  {
+    fn may_be_subset(&self, other: &Buckets) -> SKleene {
+      if self.bits == 0 { True }
+      else if self.bits & !other.bits != 0 { False }
+      else { Unknown }
+    }
+  }
+
+  impl MayProperSubset for Buckets
+  {
+    fn may_be_proper_subset(&self, other: &Buckets) -> SKleene {
+      if self.bits == 0 { SKleene::from_bool(other.bits != 0) }
+      else { self.may_be_subset(other).and(Unknown) }
+    }
+  }
+
+  #[test]
+  fn lifted_test() {
+    assert_eq!(set(&[1, 2]).may_contain(&2), True);
+    assert_eq!(set(&[1, 2]).may_be_subset(&set(&[1])), False);
+    assert_eq!(set(&[1]).may_be_proper_subset(&set(&[1, 2])), True);
+    assert_eq!(set(&[1]).may_overlap(&set(&[2])), False);
+    assert_eq!(5u32.may_be_disjoint(&6), True);
+
+    let sets = vec![set(&[]), set(&[1]), set(&[1, 2]), set(&[2, 3]), set(&[1, 2, 3]), set(&[9])];
+    MaySetTester::new(0, sets, vec![0, 1, 2, 3, 9]).test_all();
+  }
+
+  #[test]
+  fn approximate_test() {
+    assert_eq!(Buckets::new(&[1, 2]).may_contain(&10), Unknown);
+    assert_eq!(Buckets::new(&[1, 2]).may_contain(&3), False);
+    assert_eq!(Buckets::new(&[1]).may_be_subset(&Buckets::new(&[9, 4])), Unknown);
+    assert_eq!(Buckets::new(&[1, 2]).may_be_subset(&Buckets::new(&[9])), False);
+
+    let sets = vec![Buckets::new(&[]), Buckets::new(&[1]), Buckets::new(&[1, 2]),
+      Buckets::new(&[9, 10]), Buckets::new(&[3, 4, 5])];
+    MaySetTester::new(1, sets, (0..16).collect()).test_all();
+  }
+
+  #[test]
+  #[should_panic]
+  fn inconsistent_test() {
+    // `may_overlap` mistakenly answers the disjointness.
+    #[derive(Debug)]
+    struct Wrong(BTreeSet<u32>);
+    impl Collection for Wrong { type Item = u32; }
+    impl MayContain for Wrong {
+      fn may_contain(&self, value: &u32) -> SKleene { self.0.may_contain(value) }
+    }
+    impl MayOverlap for Wrong {
+      fn may_overlap(&self, other: &Wrong) -> SKleene { self.0.may_be_disjoint(&other.0) }
+    }
+    impl MayDisjoint for Wrong {
+      fn may_be_disjoint(&self, other: &Wrong) -> SKleene { self.0.may_be_disjoint(&other.0) }
+    }
+    impl MaySubset for Wrong {
+      fn may_be_subset(&self, other: &Wrong) -> SKleene { self.0.may_be_subset(&other.0) }
+    }
+    impl MayProperSubset for Wrong {
+      fn may_be_proper_subset(&self, other: &Wrong) -> SKleene { self.0.may_be_proper_subset(&other.0) }
+    }
+    MaySetTester::new(2, vec![Wrong(set(&[1])), Wrong(set(&[1, 2]))], vec![1, 2]).test_all();
+  }
+}
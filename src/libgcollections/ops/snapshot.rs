@@ -0,0 +1,17 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Collections whose state can be saved and restored, typically by a search procedure which pushes the labels on a trail and restores them when it backtracks.
+/// The labels must be restored in the reverse order of their creation: once a label is restored, the labels created after it are invalid.
+pub trait Snapshot
+{
+  type Label;
+
+  fn snapshot(&self) -> Self::Label;
+  fn restore(&mut self, label: Self::Label);
+}
@@ -10,7 +10,11 @@ use kind::*;
 use bit_set::BitSet as StdBitSet;
 use std::ops::{Deref, DerefMut};
 use ops::*;
+use wrappers::powerset::{display_set, subset_entailment};
+use trilean::SKleene;
+use std::fmt::{Formatter, Display, Error};
 
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct BitSet
 {
   bs: StdBitSet
@@ -71,3 +75,138 @@ set_op_impl! {
   Difference, difference, difference_with;
   SymmetricDifference, symmetric_difference, symmetric_difference_with
 }
+
+impl Cardinality for BitSet
+{
+  type Size = usize;
+  fn size(&self) -> usize {
+    self.bs.len()
+  }
+}
+
+impl Empty for BitSet
+{
+  fn empty() -> BitSet {
+    BitSet::wrap(StdBitSet::new())
+  }
+}
+
+impl Singleton for BitSet
+{
+  fn singleton(value: usize) -> BitSet {
+    let mut set = BitSet::empty();
+    set.bs.insert(value);
+    set
+  }
+}
+
+impl Insert for BitSet
+{
+  fn insert(&mut self, value: usize) {
+    self.bs.insert(value);
+  }
+}
+
+/// Extracts the smallest element of the set.
+impl Extract for BitSet
+{
+  fn extract(&mut self) -> Option<usize> {
+    let value = self.bs.iter().next();
+    if let Some(value) = value {
+      self.bs.remove(value);
+    }
+    value
+  }
+}
+
+macro_rules! set_predicate_impl
+{
+  ( $( $t: ident, $m:ident );* ) =>
+  {$(
+    impl $t for BitSet {
+      fn $m(&self, other: &BitSet) -> bool {
+        self.deref().$m(other)
+      }
+    }
+  )*}
+}
+
+set_predicate_impl! {
+  Disjoint, is_disjoint;
+  Subset, is_subset
+}
+
+impl ProperSubset for BitSet
+{
+  fn is_proper_subset(&self, other: &BitSet) -> bool {
+    self.bs.len() < other.bs.len() && self.bs.is_subset(other)
+  }
+}
+
+impl Overlap for BitSet
+{
+  fn overlap(&self, other: &BitSet) -> bool {
+    !self.bs.is_disjoint(other)
+  }
+}
+
+impl Display for BitSet
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    display_set(self.iter(), formatter)
+  }
+}
+
+/// Powerset lattice: the join is the union and the meet is the intersection.
+impl Join for BitSet
+{
+  fn join(mut self, other: BitSet) -> BitSet {
+    self.bs.union_with(&other);
+    self
+  }
+
+  fn join_in_place(&mut self, other: BitSet) -> bool {
+    let size = self.bs.len();
+    self.bs.union_with(&other);
+    self.bs.len() != size
+  }
+}
+
+impl Meet for BitSet
+{
+  fn meet(mut self, other: BitSet) -> BitSet {
+    self.bs.intersect_with(&other);
+    self
+  }
+
+  fn meet_in_place(&mut self, other: BitSet) -> bool {
+    let size = self.bs.len();
+    self.bs.intersect_with(&other);
+    self.bs.len() != size
+  }
+}
+
+impl Entailment for BitSet
+{
+  fn entail(&self, other: &BitSet) -> SKleene {
+    subset_entailment(self, other)
+  }
+}
+
+impl Bot for BitSet
+{
+  fn bot() -> BitSet {
+    BitSet::empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use wrappers::test::*;
+
+  #[test]
+  fn set_test() {
+    test_set(|values| BitSet::wrap(values.iter().map(|&x| x as usize).collect()));
+  }
+}
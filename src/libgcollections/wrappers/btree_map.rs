@@ -0,0 +1,151 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! When the values form a lattice, the map is a pointwise lattice where a missing key is mapped to `bot`.
+//! The join is the union of the keys and the meet is the intersection of the keys, with the join (resp. meet) applied to the values of the common keys.
+//! The keys mapped to `bot` are dropped by `wrap`, `insert` and the meet, so two maps are equal whenever they map their keys to the same values.
+
+use kind::*;
+use ops::*;
+use ops::lattice::product::product_entailment;
+use trilean::SKleene;
+use trilean::SKleene::*;
+use std::collections::BTreeMap as StdBTreeMap;
+use std::ops::Deref;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BTreeMap<K, V>
+{
+  tm: StdBTreeMap<K, V>
+}
+
+impl<K: Ord, V> BTreeMap<K, V> where
+ V: Bot + Eq
+{
+  /// The keys mapped to `bot` are dropped.
+  pub fn wrap(mut tm: StdBTreeMap<K, V>) -> BTreeMap<K, V> {
+    let bot = V::bot();
+    tm.retain(|_, value| *value != bot);
+    BTreeMap{tm: tm}
+  }
+
+  /// Maps `key` to `value` and returns the previous value of `key`, the key is removed if `value` is `bot`.
+  pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+    if value == V::bot() {
+      self.tm.remove(&key)
+    }
+    else {
+      self.tm.insert(key, value)
+    }
+  }
+}
+
+impl<K, V> Collection for BTreeMap<K, V> {
+  type Item = K;
+}
+
+impl<K, V> Deref for BTreeMap<K, V>
+{
+  type Target = StdBTreeMap<K, V>;
+
+  fn deref<'a>(&'a self) -> &'a StdBTreeMap<K, V> {
+    &self.tm
+  }
+}
+
+impl<K: Ord, V> Contains for BTreeMap<K, V>
+{
+  fn contains(&self, key: &K) -> bool {
+    self.tm.contains_key(key)
+  }
+}
+
+impl<K: Ord, V> Cardinality for BTreeMap<K, V>
+{
+  type Size = usize;
+  fn size(&self) -> usize {
+    self.tm.len()
+  }
+}
+
+impl<K: Ord, V> Empty for BTreeMap<K, V>
+{
+  fn empty() -> BTreeMap<K, V> {
+    BTreeMap{tm: StdBTreeMap::new()}
+  }
+}
+
+impl<K: Ord, V: Join> Join for BTreeMap<K, V>
+{
+  fn join(mut self, other: BTreeMap<K, V>) -> BTreeMap<K, V> {
+    for (key, value) in other.tm {
+      let value = match self.tm.remove(&key) {
+        Some(x) => x.join(value),
+        None => value
+      };
+      self.tm.insert(key, value);
+    }
+    self
+  }
+}
+
+impl<K: Ord, V> Meet for BTreeMap<K, V> where
+ V: Meet + Bot + Eq
+{
+  fn meet(self, mut other: BTreeMap<K, V>) -> BTreeMap<K, V> {
+    BTreeMap::wrap(self.tm.into_iter()
+      .filter_map(|(key, x)| other.tm.remove(&key).map(|y| (key, x.meet(y))))
+      .collect())
+  }
+}
+
+fn pointwise_entailment<K: Ord, V>(a: &BTreeMap<K, V>, b: &BTreeMap<K, V>) -> SKleene where
+ V: Entailment + Bot
+{
+  let bot = V::bot();
+  b.tm.iter().fold(True, |acc, (key, y)|
+    acc.and(a.tm.get(key).unwrap_or(&bot).entail(y)))
+}
+
+impl<K: Ord, V> Entailment for BTreeMap<K, V> where
+ V: Entailment + Bot
+{
+  fn entail(&self, other: &BTreeMap<K, V>) -> SKleene {
+    product_entailment(pointwise_entailment(self, other),
+      || pointwise_entailment(other, self))
+  }
+}
+
+impl<K: Ord, V> Bot for BTreeMap<K, V>
+{
+  fn bot() -> BTreeMap<K, V> {
+    BTreeMap::empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use wrappers::test::*;
+  use wrappers::optional::FlatOptional;
+
+  #[test]
+  fn map_test() {
+    test_map(|entries| BTreeMap::wrap(entries.into_iter().collect()));
+  }
+
+  #[test]
+  fn insert_test() {
+    let mut m = BTreeMap::<u32, FlatOptional<i32>>::empty();
+    assert_eq!(m.insert(1, known(1)), None);
+    assert_eq!(m.insert(2, FlatOptional::Bottom), None);
+    assert_eq!(m.size(), 1);
+    assert_eq!(m.insert(1, FlatOptional::Bottom), Some(known(1)));
+    assert_eq!(m, BTreeMap::bot());
+  }
+}
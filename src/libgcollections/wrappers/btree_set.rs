@@ -10,7 +10,11 @@ use kind::*;
 use std::collections::BTreeSet as StdBTreeSet;
 use std::ops::{Deref, DerefMut};
 use ops::*;
+use wrappers::powerset::{display_set, subset_entailment};
+use trilean::SKleene;
+use std::fmt::{Formatter, Display, Error};
 
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct BTreeSet<T>
 {
   ts: StdBTreeSet<T>
@@ -70,3 +74,154 @@ set_op_impl! {
   Difference, difference;
   SymmetricDifference, symmetric_difference
 }
+
+impl<T: Ord> Cardinality for BTreeSet<T>
+{
+  type Size = usize;
+  fn size(&self) -> usize {
+    self.ts.len()
+  }
+}
+
+impl<T: Ord> Empty for BTreeSet<T>
+{
+  fn empty() -> BTreeSet<T> {
+    BTreeSet::wrap(StdBTreeSet::new())
+  }
+}
+
+impl<T: Ord> Singleton for BTreeSet<T>
+{
+  fn singleton(value: T) -> BTreeSet<T> {
+    let mut set = BTreeSet::empty();
+    set.ts.insert(value);
+    set
+  }
+}
+
+impl<T: Ord> Insert for BTreeSet<T>
+{
+  fn insert(&mut self, value: T) {
+    self.ts.insert(value);
+  }
+}
+
+/// Extracts the smallest element of the set.
+impl<T: Ord> Extract for BTreeSet<T>
+{
+  fn extract(&mut self) -> Option<T> {
+    self.ts.pop_first()
+  }
+}
+
+macro_rules! set_predicate_impl
+{
+  ( $( $t: ident, $m:ident );* ) =>
+  {$(
+    impl<T: Ord> $t for BTreeSet<T>
+    {
+      fn $m(&self, other: &BTreeSet<T>) -> bool {
+        self.deref().$m(other)
+      }
+    }
+  )*}
+}
+
+set_predicate_impl! {
+  Disjoint, is_disjoint;
+  Subset, is_subset
+}
+
+impl<T: Ord> ProperSubset for BTreeSet<T>
+{
+  fn is_proper_subset(&self, other: &BTreeSet<T>) -> bool {
+    self.ts.len() < other.ts.len() && self.ts.is_subset(other)
+  }
+}
+
+impl<T: Ord> Overlap for BTreeSet<T>
+{
+  fn overlap(&self, other: &BTreeSet<T>) -> bool {
+    !self.ts.is_disjoint(other)
+  }
+}
+
+impl<T: Ord + Display> Display for BTreeSet<T>
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    display_set(self.iter(), formatter)
+  }
+}
+
+/// Powerset lattice: the join is the union and the meet is the intersection.
+impl<T: Ord> Join for BTreeSet<T>
+{
+  fn join(mut self, other: BTreeSet<T>) -> BTreeSet<T> {
+    self.ts.extend(other.ts);
+    self
+  }
+
+  fn join_in_place(&mut self, other: BTreeSet<T>) -> bool {
+    let size = self.ts.len();
+    self.ts.extend(other.ts);
+    self.ts.len() != size
+  }
+}
+
+impl<T: Ord> Meet for BTreeSet<T>
+{
+  fn meet(mut self, other: BTreeSet<T>) -> BTreeSet<T> {
+    self.ts.retain(|x| other.ts.contains(x));
+    self
+  }
+
+  fn meet_in_place(&mut self, other: BTreeSet<T>) -> bool {
+    let size = self.ts.len();
+    self.ts.retain(|x| other.ts.contains(x));
+    self.ts.len() != size
+  }
+}
+
+impl<T: Ord> Entailment for BTreeSet<T>
+{
+  fn entail(&self, other: &BTreeSet<T>) -> SKleene {
+    subset_entailment(self, other)
+  }
+}
+
+impl<T: Ord> Bot for BTreeSet<T>
+{
+  fn bot() -> BTreeSet<T> {
+    BTreeSet::empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use wrappers::test::*;
+
+  fn set(values: &[i32]) -> BTreeSet<i32> {
+    BTreeSet::wrap(values.iter().cloned().collect())
+  }
+
+  #[test]
+  fn set_test() {
+    test_set(|values| BTreeSet::wrap(values.iter().map(|&x| x as i32).collect()));
+  }
+
+  #[test]
+  fn semilattice_test() {
+    use ops::lattice::test::*;
+    use trilean::SKleene::*;
+    // Without a universe, the sets only form a join-semilattice with `bot`, and their dual a meet-semilattice with `top`.
+    let data_a = vec![set(&[]), set(&[1]), set(&[1,2]), set(&[1,2]), set(&[4])];
+    let data_b = vec![set(&[1]), set(&[1]), set(&[2,3]), set(&[2]), set(&[1,2,3])];
+    JoinSemilatticeTester::new(0, data_a.clone(), data_b.clone(),
+      vec![False, True, Unknown, True, Unknown],
+      vec![set(&[1]), set(&[1]), set(&[1,2,3]), set(&[1,2]), set(&[1,2,3,4])])
+      .test_all();
+    let dual = |data: Vec<BTreeSet<i32>>| data.into_iter().map(Dual::wrap).collect();
+    MeetSemilatticeTester::from_data(1, dual(data_a), dual(data_b)).test_all();
+  }
+}
@@ -0,0 +1,186 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! When the values form a lattice, the map is a pointwise lattice where a missing key is mapped to `bot`.
+//! The join is the union of the keys and the meet is the intersection of the keys, with the join (resp. meet) applied to the values of the common keys.
+//! The keys mapped to `bot` are dropped by `wrap`, `insert` and the meet, so two maps are equal whenever they map their keys to the same values.
+
+use kind::*;
+use ops::*;
+use ops::lattice::product::product_entailment;
+use trilean::SKleene;
+use trilean::SKleene::*;
+use std::collections::HashMap as StdHashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::ops::Deref;
+
+#[derive(Clone, Debug)]
+pub struct HashMap<K, V, S = RandomState>
+{
+  hm: StdHashMap<K, V, S>
+}
+
+impl<K, V, S> HashMap<K, V, S> where
+  K: Eq + Hash,
+  V: Bot + Eq,
+  S: BuildHasher
+{
+  /// The keys mapped to `bot` are dropped.
+  pub fn wrap(mut hm: StdHashMap<K, V, S>) -> HashMap<K, V, S> {
+    let bot = V::bot();
+    hm.retain(|_, value| *value != bot);
+    HashMap{hm: hm}
+  }
+
+  /// Maps `key` to `value` and returns the previous value of `key`, the key is removed if `value` is `bot`.
+  pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+    if value == V::bot() {
+      self.hm.remove(&key)
+    }
+    else {
+      self.hm.insert(key, value)
+    }
+  }
+}
+
+impl<K, V, S> PartialEq for HashMap<K, V, S> where
+  K: Eq + Hash,
+  V: PartialEq,
+  S: BuildHasher
+{
+  fn eq(&self, other: &HashMap<K, V, S>) -> bool {
+    self.hm == other.hm
+  }
+}
+
+impl<K, V, S> Eq for HashMap<K, V, S> where
+  K: Eq + Hash,
+  V: Eq,
+  S: BuildHasher
+{}
+
+impl<K, V, S> Collection for HashMap<K, V, S> {
+  type Item = K;
+}
+
+impl<K, V, S> Deref for HashMap<K, V, S>
+{
+  type Target = StdHashMap<K, V, S>;
+
+  fn deref<'a>(&'a self) -> &'a StdHashMap<K, V, S> {
+    &self.hm
+  }
+}
+
+impl<K, V, S> Contains for HashMap<K, V, S> where
+ K: Eq + Hash,
+ S: BuildHasher
+{
+  fn contains(&self, key: &K) -> bool {
+    self.hm.contains_key(key)
+  }
+}
+
+impl<K, V, S> Cardinality for HashMap<K, V, S>
+{
+  type Size = usize;
+  fn size(&self) -> usize {
+    self.hm.len()
+  }
+}
+
+impl<K, V, S> Empty for HashMap<K, V, S> where
+ K: Eq + Hash,
+ S: BuildHasher + Default
+{
+  fn empty() -> HashMap<K, V, S> {
+    HashMap{hm: StdHashMap::default()}
+  }
+}
+
+impl<K, V, S> Join for HashMap<K, V, S> where
+ K: Eq + Hash,
+ S: BuildHasher,
+ V: Join
+{
+  fn join(mut self, other: HashMap<K, V, S>) -> HashMap<K, V, S> {
+    for (key, value) in other.hm {
+      let value = match self.hm.remove(&key) {
+        Some(x) => x.join(value),
+        None => value
+      };
+      self.hm.insert(key, value);
+    }
+    self
+  }
+}
+
+impl<K, V, S> Meet for HashMap<K, V, S> where
+ K: Eq + Hash,
+ S: BuildHasher + Default,
+ V: Meet + Bot + Eq
+{
+  fn meet(self, mut other: HashMap<K, V, S>) -> HashMap<K, V, S> {
+    HashMap::wrap(self.hm.into_iter()
+      .filter_map(|(key, x)| other.hm.remove(&key).map(|y| (key, x.meet(y))))
+      .collect())
+  }
+}
+
+fn pointwise_entailment<K, V, S>(a: &HashMap<K, V, S>, b: &HashMap<K, V, S>) -> SKleene where
+ K: Eq + Hash,
+ S: BuildHasher,
+ V: Entailment + Bot
+{
+  let bot = V::bot();
+  b.hm.iter().fold(True, |acc, (key, y)|
+    acc.and(a.hm.get(key).unwrap_or(&bot).entail(y)))
+}
+
+impl<K, V, S> Entailment for HashMap<K, V, S> where
+ K: Eq + Hash,
+ S: BuildHasher,
+ V: Entailment + Bot
+{
+  fn entail(&self, other: &HashMap<K, V, S>) -> SKleene {
+    product_entailment(pointwise_entailment(self, other),
+      || pointwise_entailment(other, self))
+  }
+}
+
+impl<K, V, S> Bot for HashMap<K, V, S> where
+ K: Eq + Hash,
+ S: BuildHasher + Default
+{
+  fn bot() -> HashMap<K, V, S> {
+    HashMap::empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use wrappers::test::*;
+  use wrappers::optional::FlatOptional;
+
+  #[test]
+  fn map_test() {
+    test_map(|entries| HashMap::<_, _, RandomState>::wrap(entries.into_iter().collect()));
+  }
+
+  #[test]
+  fn insert_test() {
+    let mut m = HashMap::<u32, FlatOptional<i32>>::empty();
+    assert_eq!(m.insert(1, known(1)), None);
+    assert_eq!(m.insert(2, FlatOptional::Bottom), None);
+    assert_eq!(m.size(), 1);
+    assert_eq!(m.insert(1, FlatOptional::Bottom), Some(known(1)));
+    assert_eq!(m, HashMap::bot());
+  }
+}
@@ -12,7 +12,11 @@ use std::collections::hash_map::RandomState;
 use std::hash::{BuildHasher, Hash};
 use std::ops::{Deref, DerefMut};
 use ops::*;
+use wrappers::powerset::{display_set, subset_entailment};
+use trilean::SKleene;
+use std::fmt::{Formatter, Display, Error};
 
+#[derive(Clone, Debug)]
 pub struct HashSet<T, S = RandomState>
 {
   hs: StdHashSet<T, S>
@@ -27,6 +31,20 @@ impl<T, S> HashSet<T, S> where
   }
 }
 
+impl<T, S> PartialEq for HashSet<T, S> where
+  T: Eq + Hash,
+  S: BuildHasher
+{
+  fn eq(&self, other: &HashSet<T, S>) -> bool {
+    self.hs == other.hs
+  }
+}
+
+impl<T, S> Eq for HashSet<T, S> where
+  T: Eq + Hash,
+  S: BuildHasher
+{}
+
 impl<T, S> Collection for HashSet<T, S> {
   type Item = T;
 }
@@ -77,3 +95,159 @@ set_op_impl! {
   Difference, difference;
   SymmetricDifference, symmetric_difference
 }
+
+impl<T, S> Cardinality for HashSet<T, S>
+{
+  type Size = usize;
+  fn size(&self) -> usize {
+    self.hs.len()
+  }
+}
+
+impl<T, S> Empty for HashSet<T, S> where
+ T: Eq + Hash,
+ S: BuildHasher + Default
+{
+  fn empty() -> HashSet<T, S> {
+    HashSet::wrap(StdHashSet::default())
+  }
+}
+
+impl<T, S> Singleton for HashSet<T, S> where
+ T: Eq + Hash,
+ S: BuildHasher + Default
+{
+  fn singleton(value: T) -> HashSet<T, S> {
+    let mut set = HashSet::empty();
+    set.hs.insert(value);
+    set
+  }
+}
+
+impl<T, S> Insert for HashSet<T, S> where
+ T: Eq + Hash,
+ S: BuildHasher
+{
+  fn insert(&mut self, value: T) {
+    self.hs.insert(value);
+  }
+}
+
+/// Extracts an arbitrary element of the set.
+impl<T, S> Extract for HashSet<T, S> where
+ T: Eq + Hash + Clone,
+ S: BuildHasher
+{
+  fn extract(&mut self) -> Option<T> {
+    let value = self.hs.iter().next().cloned();
+    value.and_then(|value| self.hs.take(&value))
+  }
+}
+
+macro_rules! set_predicate_impl
+{
+  ( $( $t: ident, $m:ident );* ) =>
+  {$(
+    impl<T, S> $t for HashSet<T, S> where
+     T: Eq + Hash,
+     S: BuildHasher
+    {
+      fn $m(&self, other: &HashSet<T, S>) -> bool {
+        self.deref().$m(other)
+      }
+    }
+  )*}
+}
+
+set_predicate_impl! {
+  Disjoint, is_disjoint;
+  Subset, is_subset
+}
+
+impl<T, S> ProperSubset for HashSet<T, S> where
+ T: Eq + Hash,
+ S: BuildHasher
+{
+  fn is_proper_subset(&self, other: &HashSet<T, S>) -> bool {
+    self.hs.len() < other.hs.len() && self.hs.is_subset(other)
+  }
+}
+
+impl<T, S> Overlap for HashSet<T, S> where
+ T: Eq + Hash,
+ S: BuildHasher
+{
+  fn overlap(&self, other: &HashSet<T, S>) -> bool {
+    !self.hs.is_disjoint(other)
+  }
+}
+
+impl<T, S> Display for HashSet<T, S> where
+ T: Display
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    display_set(self.hs.iter(), formatter)
+  }
+}
+
+/// Powerset lattice: the join is the union and the meet is the intersection.
+impl<T, S> Join for HashSet<T, S> where
+ T: Eq + Hash,
+ S: BuildHasher
+{
+  fn join(mut self, other: HashSet<T, S>) -> HashSet<T, S> {
+    self.hs.extend(other.hs);
+    self
+  }
+
+  fn join_in_place(&mut self, other: HashSet<T, S>) -> bool {
+    let size = self.hs.len();
+    self.hs.extend(other.hs);
+    self.hs.len() != size
+  }
+}
+
+impl<T, S> Meet for HashSet<T, S> where
+ T: Eq + Hash,
+ S: BuildHasher
+{
+  fn meet(mut self, other: HashSet<T, S>) -> HashSet<T, S> {
+    self.hs.retain(|x| other.hs.contains(x));
+    self
+  }
+
+  fn meet_in_place(&mut self, other: HashSet<T, S>) -> bool {
+    let size = self.hs.len();
+    self.hs.retain(|x| other.hs.contains(x));
+    self.hs.len() != size
+  }
+}
+
+impl<T, S> Entailment for HashSet<T, S> where
+ T: Eq + Hash,
+ S: BuildHasher
+{
+  fn entail(&self, other: &HashSet<T, S>) -> SKleene {
+    subset_entailment(self, other)
+  }
+}
+
+impl<T, S> Bot for HashSet<T, S> where
+ T: Eq + Hash,
+ S: BuildHasher + Default
+{
+  fn bot() -> HashSet<T, S> {
+    HashSet::empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use wrappers::test::*;
+
+  #[test]
+  fn set_test() {
+    test_set(|values| HashSet::<i32>::wrap(values.iter().map(|&x| x as i32).collect()));
+  }
+}
@@ -7,9 +7,17 @@
 // except according to those terms.
 
 pub mod bit_set;
+pub mod btree_map;
 pub mod btree_set;
+pub mod hash_map;
 pub mod hash_set;
 pub mod optional;
+pub mod order;
+pub mod powerset;
 pub mod primitives;
+pub mod sparse_set;
 pub mod vector;
 pub mod vector_deque;
+
+#[cfg(test)]
+pub mod test;
@@ -8,7 +8,9 @@
 
 use kind::*;
 use ops::*;
+use wrappers::powerset::display_set;
 use std::ops::*;
+use std::fmt::{Formatter, Display, Error};
 
 fn binary_map_unwrap_or<T, U, R, F>(x: &Optional<T>, y: &Optional<U>, default: R, f: F) -> R where
  F: FnOnce(&T, &U) -> R
@@ -61,6 +63,13 @@ impl<T> Optional<T>
   }
 }
 
+impl<T: Display> Display for Optional<T>
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    display_set(self.iter(), formatter)
+  }
+}
+
 impl<T> Collection for Optional<T> {
   type Item = T;
 }
@@ -414,6 +423,10 @@ macro_rules! mul_optional_arithmetics
 
 mul_optional_arithmetics!(i8,u8,i16,u16,i32,u32,i64,u64,isize,usize,f32,f64,bool,char);
 
+/// Flat lattice over `Optional<T>`: every optional value (including the empty one) is incomparable with the others, `Bottom` (unknown) is below all of them and `Topmost` (conflict) is above all of them.
+/// It does not change the set-based semantics of `Optional`, which is only used as the payload of `Value`.
+pub type FlatOptional<T> = Flat<Optional<T>>;
+
 #[allow(non_upper_case_globals)]
 #[cfg(test)]
 mod tests {
@@ -569,6 +582,23 @@ mod tests {
     }
   }
 
+  #[test]
+  fn flat_lattice_test() {
+    use ops::lattice::test::*;
+    use ops::lattice::combinators::Flat::*;
+    use trilean::SKleene::*;
+    let none: FlatOptional<i32> = Value(empty);
+    let k0: FlatOptional<i32> = Value(zero);
+    let k10: FlatOptional<i32> = Value(ten);
+    let data_a =          vec![Bottom, Bottom, none,    k0,   k0,      k0,    k10,    Topmost, Topmost];
+    let data_b =          vec![Bottom, k0,     k0,      k0,   k10,     Topmost, Bottom, k0,    Topmost];
+    let expected_entail = vec![True,   False,  Unknown, True, Unknown, False, True,   True,    True];
+    let expected_join =   vec![Bottom, k0,     Topmost, k0,   Topmost, Topmost, k10,  Topmost, Topmost];
+    let expected_meet =   vec![Bottom, Bottom, Bottom,  k0,   Bottom,  k0,    Bottom, k0,      Topmost];
+    let tester = LatticeTester::new(0, data_a, data_b, expected_entail, expected_join, expected_meet);
+    tester.test_all();
+  }
+
   #[test]
   fn arithmetics_tests() {
     let twenty = Optional::singleton(20);
@@ -0,0 +1,363 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Lattices given by the natural order of the primitive types, they are chains and thus distributive lattices.
+//!
+//! * `Max<T>` is ordered by `<=`: the join is the maximum and the meet is the minimum.
+//! * `Min<T>` is ordered by `>=`: the join is the minimum and the meet is the maximum.
+//! * `Bool` is ordered by the implication (`false` is below `true`): the join is the disjunction and the meet is the conjunction. The reverse implication order is given by `Dual<Bool>`.
+//!
+//! `Max<T>` and `Min<T>` are defined for every integer and floating-point type (see `OrderValue`).
+//! The bounds of the integers are `MIN` and `MAX`, and the bounds of the floating-point types are the infinities.
+//! `wrap` panics on `NaN` since the order would not be total and the lattice laws would not hold.
+//!
+//! ```rust
+//! use gcollections::ops::*;
+//! use gcollections::Max;
+//!
+//! let a = Max::wrap(3);
+//! let b = Max::wrap(5);
+//! assert_eq!(a.join(b), b);
+//! assert_eq!(a.meet(b), a);
+//! assert_eq!(b.entail(&a), trilean::SKleene::True);
+//! ```
+
+use ops::*;
+use trilean::SKleene;
+use trilean::SKleene::*;
+use std::cmp::Ordering;
+use std::fmt::{Formatter, Display, Error};
+use std::ops::{Deref, DerefMut};
+
+/// Primitive types which are totally ordered by `<=`, the floating-point types being restricted to the values other than `NaN`.
+pub trait OrderValue: PartialOrd
+{
+  fn min_value() -> Self;
+  fn max_value() -> Self;
+  fn is_nan(&self) -> bool;
+}
+
+macro_rules! integer_order_value
+{
+  ( $( $t:ident ),* ) =>
+  {$(
+    impl OrderValue for $t
+    {
+      fn min_value() -> $t { $t::MIN }
+      fn max_value() -> $t { $t::MAX }
+      fn is_nan(&self) -> bool { false }
+    }
+  )*}
+}
+
+macro_rules! float_order_value
+{
+  ( $( $t:ident ),* ) =>
+  {$(
+    impl OrderValue for $t
+    {
+      fn min_value() -> $t { $t::NEG_INFINITY }
+      fn max_value() -> $t { $t::INFINITY }
+      fn is_nan(&self) -> bool { $t::is_nan(*self) }
+    }
+  )*}
+}
+
+integer_order_value!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+float_order_value!(f32, f64);
+
+fn order_entailment<T: PartialOrd>(a: &T, b: &T) -> SKleene {
+  match a.partial_cmp(b) {
+    Some(Ordering::Greater) | Some(Ordering::Equal) => True,
+    Some(Ordering::Less) => False,
+    None => Unknown
+  }
+}
+
+fn reverse_order_entailment<T: PartialOrd>(a: &T, b: &T) -> SKleene {
+  order_entailment(b, a)
+}
+
+macro_rules! order_wrapper
+{
+  ( $name:ident, $join:ident, $meet:ident, $top:ident, $bot:ident, $doc:expr ) =>
+  {
+    #[doc = $doc]
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub struct $name<T>
+    {
+      value: T
+    }
+
+    impl<T: OrderValue> $name<T>
+    {
+      pub fn wrap(value: T) -> $name<T> {
+        assert!(!value.is_nan(), "`NaN` is not ordered, it cannot be wrapped in `{}`.", stringify!($name));
+        $name {
+          value: value
+        }
+      }
+
+      pub fn unwrap(self) -> T {
+        self.value
+      }
+    }
+
+    impl<T> Deref for $name<T>
+    {
+      type Target = T;
+
+      fn deref<'a>(&'a self) -> &'a T {
+        &self.value
+      }
+    }
+
+    impl<T: Display> Display for $name<T>
+    {
+      fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        self.value.fmt(formatter)
+      }
+    }
+
+    impl<T: PartialOrd> Join for $name<T>
+    {
+      fn join(self, other: $name<T>) -> $name<T> {
+        if $join(&self.value, &other.value) == True { self } else { other }
+      }
+    }
+
+    impl<T: PartialOrd> Meet for $name<T>
+    {
+      fn meet(self, other: $name<T>) -> $name<T> {
+        if $meet(&self.value, &other.value) == True { self } else { other }
+      }
+    }
+
+    impl<T: PartialOrd> Entailment for $name<T>
+    {
+      fn entail(&self, other: &$name<T>) -> SKleene {
+        $join(&self.value, &other.value)
+      }
+    }
+
+    impl<T: OrderValue> Top for $name<T>
+    {
+      fn top() -> $name<T> {
+        $name::wrap(T::$top())
+      }
+    }
+
+    impl<T: OrderValue> Bot for $name<T>
+    {
+      fn bot() -> $name<T> {
+        $name::wrap(T::$bot())
+      }
+    }
+  }
+}
+
+order_wrapper!(Max, order_entailment, reverse_order_entailment, max_value, min_value,
+  "Lattice ordered by `<=`, the join is the maximum and the meet the minimum.");
+order_wrapper!(Min, reverse_order_entailment, order_entailment, min_value, max_value,
+  "Lattice ordered by `>=`, the join is the minimum and the meet the maximum.");
+
+/// The equality is total on the floating-point types since `wrap` rejects `NaN`.
+macro_rules! order_eq_impl
+{
+  ( $( $t:ty ),* ) =>
+  {$(
+    impl Eq for Max<$t> {}
+    impl Eq for Min<$t> {}
+  )*}
+}
+
+order_eq_impl!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64);
+
+/// Lattice of the booleans ordered by the implication: `a.entail(&b)` holds if `b` implies `a`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Bool
+{
+  value: bool
+}
+
+impl Bool
+{
+  pub fn wrap(value: bool) -> Bool {
+    Bool {
+      value: value
+    }
+  }
+
+  pub fn unwrap(self) -> bool {
+    self.value
+  }
+}
+
+impl Deref for Bool
+{
+  type Target = bool;
+
+  fn deref<'a>(&'a self) -> &'a bool {
+    &self.value
+  }
+}
+
+impl DerefMut for Bool
+{
+  fn deref_mut<'a>(&'a mut self) -> &'a mut bool {
+    &mut self.value
+  }
+}
+
+impl Display for Bool
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    self.value.fmt(formatter)
+  }
+}
+
+impl Join for Bool
+{
+  fn join(self, other: Bool) -> Bool {
+    Bool::wrap(self.value || other.value)
+  }
+}
+
+impl Meet for Bool
+{
+  fn meet(self, other: Bool) -> Bool {
+    Bool::wrap(self.value && other.value)
+  }
+}
+
+impl Entailment for Bool
+{
+  fn entail(&self, other: &Bool) -> SKleene {
+    SKleene::from_bool(self.value || !other.value)
+  }
+}
+
+impl Top for Bool
+{
+  fn top() -> Bool {
+    Bool::wrap(true)
+  }
+}
+
+impl Bot for Bool
+{
+  fn bot() -> Bool {
+    Bool::wrap(false)
+  }
+}
+
+impl Widen for Bool {}
+impl Narrow for Bool {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ops::lattice::test::*;
+
+  #[test]
+  fn max_test() {
+    let m = Max::wrap;
+    let data_a =          vec![m(0), m(1), m(2),  m(-5), m(i32::MAX), m(i32::MIN)];
+    let data_b =          vec![m(0), m(2), m(1),  m(3),  m(0),        m(0)];
+    let expected_entail = vec![True, False, True, False, True,        False];
+    let expected_join =   vec![m(0), m(2), m(2),  m(3),  m(i32::MAX), m(0)];
+    let expected_meet =   vec![m(0), m(1), m(1),  m(-5), m(0),        m(i32::MIN)];
+    LatticeTester::new(0, data_a, data_b, expected_entail, expected_join, expected_meet)
+      .distributive()
+      .test_all();
+    RandomLatticeTester::new(1, |rng: &mut Rng| Max::wrap(i8::arbitrary(rng)))
+      .seed(test_seed(1))
+      .distributive()
+      .test_all();
+    RandomLatticeTester::new(2, |rng: &mut Rng| Max::wrap(u64::arbitrary(rng)))
+      .seed(test_seed(2))
+      .distributive()
+      .test_all();
+  }
+
+  #[test]
+  fn min_test() {
+    let m = Min::wrap;
+    let data_a =          vec![m(0), m(1),  m(2),    m(5), m(u8::MAX), m(u8::MIN)];
+    let data_b =          vec![m(0), m(2),  m(1),    m(3), m(0),       m(0)];
+    let expected_entail = vec![True, True,  False,   False, False,     True];
+    let expected_join =   vec![m(0), m(1),  m(1),    m(3), m(0),       m(0)];
+    let expected_meet =   vec![m(0), m(2),  m(2),    m(5), m(u8::MAX), m(0)];
+    LatticeTester::new(0, data_a, data_b, expected_entail, expected_join, expected_meet)
+      .distributive()
+      .test_all();
+    RandomLatticeTester::new(1, |rng: &mut Rng| Min::wrap(i64::arbitrary(rng)))
+      .seed(test_seed(3))
+      .distributive()
+      .test_all();
+  }
+
+  #[test]
+  fn float_test() {
+    let m = Max::wrap;
+    let data_a = vec![m(0.0), m(1.5),  m(-2.25), m(f64::MAX), m(f64::MIN)];
+    let data_b = vec![m(0.0), m(-1.5), m(3.0),   m(1.0),      m(-0.5)];
+    LatticeTester::from_data(0, data_a, data_b).distributive().test_all();
+    assert_eq!(m(1.5).join(m(-1.5)), m(1.5));
+    assert_eq!(Max::wrap(1.5f32).meet(Max::wrap(-1.5)), Max::wrap(-1.5));
+
+    let m = Min::wrap;
+    let data_a = vec![m(0.0f32), m(1.5),  m(-2.25), m(f32::MAX), m(f32::MIN)];
+    let data_b = vec![m(0.0),    m(-1.5), m(3.0),   m(1.0),      m(-0.5)];
+    LatticeTester::from_data(1, data_a, data_b).distributive().test_all();
+    assert_eq!(m(1.5).join(m(-1.5)), m(-1.5));
+    assert_eq!(Min::<f32>::top(), m(f32::NEG_INFINITY));
+    assert_eq!(Min::<f32>::bot(), m(f32::INFINITY));
+    assert_eq!(Max::<f64>::top(), Max::wrap(f64::INFINITY));
+    assert_eq!(Max::<f64>::bot(), Max::wrap(f64::NEG_INFINITY));
+    assert_eq!(Max::<f64>::top().entail(&Max::wrap(f64::MAX)), True);
+    assert_eq!(Max::wrap(f64::MIN).entail(&Max::<f64>::bot()), True);
+  }
+
+  #[test]
+  #[should_panic(expected = "`NaN` is not ordered")]
+  fn nan_max_test() {
+    Max::wrap(f64::NAN);
+  }
+
+  #[test]
+  #[should_panic(expected = "`NaN` is not ordered")]
+  fn nan_min_test() {
+    Min::wrap(f32::NAN);
+  }
+
+  #[test]
+  fn bool_test() {
+    let b = Bool::wrap;
+    let data_a =          vec![b(false), b(false), b(true), b(true)];
+    let data_b =          vec![b(false), b(true),  b(false), b(true)];
+    let expected_entail = vec![True,     False,    True,     True];
+    let expected_join =   vec![b(false), b(true),  b(true),  b(true)];
+    let expected_meet =   vec![b(false), b(false), b(false), b(true)];
+    LatticeTester::new(0, data_a.clone(), data_b.clone(), expected_entail, expected_join, expected_meet)
+      .distributive()
+      .test_all();
+
+    // Reverse implication order: `true` is below `false`.
+    let d = |x: &Bool| Dual::wrap(*x);
+    let data_a: Vec<_> = data_a.iter().map(d).collect();
+    let data_b: Vec<_> = data_b.iter().map(d).collect();
+    let expected_entail = vec![True, True, False, True];
+    let expected_join =   vec![d(&b(false)), d(&b(false)), d(&b(false)), d(&b(true))];
+    let expected_meet =   vec![d(&b(false)), d(&b(true)),  d(&b(true)),  d(&b(true))];
+    LatticeTester::new(1, data_a, data_b, expected_entail, expected_join, expected_meet)
+      .distributive()
+      .test_all();
+    assert_eq!(Dual::<Bool>::bot(), d(&b(true)));
+  }
+}
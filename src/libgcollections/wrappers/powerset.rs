@@ -0,0 +1,400 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The sets `HashSet`, `BTreeSet` and `BitSet` are ordered by inclusion and form a lattice (the powerset lattice) where the join is the union and the meet is the intersection.
+//! The bottom element is the empty set, but the top element is the set of all the values of the type, which is generally not representable.
+//! `Powerset<S, U>` bounds a set `S` with a universe `U` declared at the type level, which provides the top element and the complement of a set.
+//!
+//! ```rust
+//! use gcollections::*;
+//! use gcollections::ops::*;
+//! use gcollections::wrappers::powerset::*;
+//!
+//! struct Digits;
+//!
+//! impl Universe<BitSet> for Digits {
+//!   fn universe() -> BitSet {
+//!     BitSet::wrap((0..10).collect())
+//!   }
+//! }
+//!
+//! type DigitSet = Powerset<BitSet, Digits>;
+//!
+//! let top = DigitSet::top();
+//! assert_eq!(top.size(), 10);
+//! assert_eq!(top.clone().meet(DigitSet::bot()), DigitSet::bot());
+//! assert_eq!(DigitSet::bot().complement(), top);
+//! ```
+
+use kind::*;
+use ops::*;
+use trilean::SKleene;
+use trilean::SKleene::*;
+use std::ops::Deref;
+use std::marker::PhantomData;
+use std::fmt::{Formatter, Display, Debug, Error};
+
+/// Provides the set of all the values that a set of type `S` can contain.
+pub trait Universe<S> {
+  fn universe() -> S;
+}
+
+/// Entailment of the powerset lattice: `a |= b` holds if `a` is a superset of `b`, `b |= a` holds if `a` is a subset of `b` and the sets are unordered otherwise.
+pub fn subset_entailment<S: Subset>(a: &S, b: &S) -> SKleene {
+  if b.is_subset(a) { True }
+  else if a.is_subset(b) { False }
+  else { Unknown }
+}
+
+pub fn display_set<I>(values: I, formatter: &mut Formatter) -> Result<(), Error> where
+ I: Iterator,
+ I::Item: Display
+{
+  formatter.write_str("{")?;
+  for (i, value) in values.enumerate() {
+    if i > 0 {
+      formatter.write_str(", ")?;
+    }
+    value.fmt(formatter)?;
+  }
+  formatter.write_str("}")
+}
+
+/// The elements of the set belong to the universe `U`, which is enforced by `wrap`.
+/// The set is not mutable in place, otherwise elements outside of `U` could be added.
+pub struct Powerset<S, U>
+{
+  set: S,
+  phantom_universe: PhantomData<U>
+}
+
+impl<S, U> Powerset<S, U>
+{
+  /// The elements of `set` outside of the universe are dropped.
+  pub fn wrap(set: S) -> Self where
+   S: Intersection<Output=S>,
+   U: Universe<S>
+  {
+    Powerset::from_subset(U::universe().intersection(&set))
+  }
+
+  /// `set` must be included in the universe, which is the case of the results of the set operations on subsets of the universe.
+  fn from_subset(set: S) -> Self {
+    Powerset {
+      set: set,
+      phantom_universe: PhantomData
+    }
+  }
+
+  pub fn unwrap(self) -> S {
+    self.set
+  }
+}
+
+impl<S: Clone, U> Clone for Powerset<S, U>
+{
+  fn clone(&self) -> Self {
+    Powerset::from_subset(self.set.clone())
+  }
+}
+
+impl<S: PartialEq, U> PartialEq for Powerset<S, U>
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.set == other.set
+  }
+}
+
+impl<S: Eq, U> Eq for Powerset<S, U> {}
+
+impl<S: Debug, U> Debug for Powerset<S, U>
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    self.set.fmt(formatter)
+  }
+}
+
+impl<S: Display, U> Display for Powerset<S, U>
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    self.set.fmt(formatter)
+  }
+}
+
+impl<S: Collection, U> Collection for Powerset<S, U> {
+  type Item = S::Item;
+}
+
+impl<S, U> Deref for Powerset<S, U>
+{
+  type Target = S;
+
+  fn deref<'a>(&'a self) -> &'a S {
+    &self.set
+  }
+}
+
+impl<S: Contains, U> Contains for Powerset<S, U>
+{
+  fn contains(&self, value: &S::Item) -> bool {
+    self.set.contains(value)
+  }
+}
+
+macro_rules! set_op_impl
+{
+  ( $( $t: ident, $m:ident );* ) =>
+  {$(
+    impl<S, U> $t for Powerset<S, U> where
+     S: $t<Output=S>
+    {
+      type Output = Powerset<S, U>;
+
+      fn $m(&self, other: &Self) -> Self {
+        Powerset::from_subset(self.set.$m(&other.set))
+      }
+    }
+  )*}
+}
+
+set_op_impl! {
+  Intersection, intersection;
+  Union, union;
+  Difference, difference;
+  SymmetricDifference, symmetric_difference
+}
+
+/// The complement is the difference with the universe.
+impl<S, U> Complement for Powerset<S, U> where
+ S: Difference<Output=S>,
+ U: Universe<S>
+{
+  fn complement(&self) -> Self {
+    Powerset::from_subset(U::universe().difference(&self.set))
+  }
+}
+
+macro_rules! set_predicate_impl
+{
+  ( $( $t: ident, $m:ident );* ) =>
+  {$(
+    impl<S: $t, U> $t for Powerset<S, U>
+    {
+      fn $m(&self, other: &Self) -> bool {
+        self.set.$m(&other.set)
+      }
+    }
+  )*}
+}
+
+set_predicate_impl! {
+  Disjoint, is_disjoint;
+  Subset, is_subset;
+  ProperSubset, is_proper_subset;
+  Overlap, overlap
+}
+
+impl<S: Cardinality, U> Cardinality for Powerset<S, U>
+{
+  type Size = S::Size;
+  fn size(&self) -> S::Size {
+    self.set.size()
+  }
+}
+
+impl<S: Empty, U> Empty for Powerset<S, U>
+{
+  fn empty() -> Self {
+    Powerset::from_subset(S::empty())
+  }
+}
+
+/// `S` must implement `Entailment` and `Clone` to forward the in-place join and meet to the set.
+impl<S, U> Join for Powerset<S, U> where
+ S: Join + Entailment + Clone
+{
+  fn join(self, other: Self) -> Self {
+    Powerset::from_subset(self.set.join(other.set))
+  }
+
+  fn join_in_place(&mut self, other: Self) -> bool {
+    self.set.join_in_place(other.set)
+  }
+}
+
+impl<S, U> Meet for Powerset<S, U> where
+ S: Meet + Entailment + Clone
+{
+  fn meet(self, other: Self) -> Self {
+    Powerset::from_subset(self.set.meet(other.set))
+  }
+
+  fn meet_in_place(&mut self, other: Self) -> bool {
+    self.set.meet_in_place(other.set)
+  }
+}
+
+impl<S: Entailment, U> Entailment for Powerset<S, U>
+{
+  fn entail(&self, other: &Self) -> SKleene {
+    self.set.entail(&other.set)
+  }
+}
+
+impl<S, U> Top for Powerset<S, U> where
+ U: Universe<S>
+{
+  fn top() -> Self {
+    Powerset::from_subset(U::universe())
+  }
+}
+
+impl<S: Bot, U> Bot for Powerset<S, U>
+{
+  fn bot() -> Self {
+    Powerset::from_subset(S::bot())
+  }
+}
+
+/// The universe is finite, thus the join and meet are a widening and a narrowing.
+impl<S: Join + Entailment + Clone, U> Widen for Powerset<S, U> {}
+impl<S: Meet + Entailment + Clone, U> Narrow for Powerset<S, U> {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ops::lattice::test::*;
+  use wrappers::btree_set::BTreeSet;
+  use wrappers::hash_set::HashSet;
+  use wrappers::bit_set::BitSet;
+
+  struct ZeroToThree;
+
+  impl Universe<BTreeSet<u32>> for ZeroToThree {
+    fn universe() -> BTreeSet<u32> {
+      BTreeSet::wrap((0..4).collect())
+    }
+  }
+
+  impl Universe<HashSet<u32>> for ZeroToThree {
+    fn universe() -> HashSet<u32> {
+      HashSet::wrap((0..4).collect())
+    }
+  }
+
+  impl Universe<BitSet> for ZeroToThree {
+    fn universe() -> BitSet {
+      BitSet::wrap((0..4).collect())
+    }
+  }
+
+  // Each row is: a, b, a |= b, a join b, a meet b.
+  type TestRow = (Vec<u32>, Vec<u32>, SKleene, Vec<u32>, Vec<u32>);
+
+  fn test_table() -> Vec<TestRow> {
+    vec![
+      (vec![], vec![], True, vec![], vec![]),
+      (vec![], vec![1], False, vec![1], vec![]),
+      (vec![1], vec![], True, vec![1], vec![]),
+      (vec![1], vec![1], True, vec![1], vec![1]),
+      (vec![1], vec![2], Unknown, vec![1,2], vec![]),
+      (vec![1,2], vec![2,3], Unknown, vec![1,2,3], vec![2]),
+      (vec![1,2,3], vec![2,3], True, vec![1,2,3], vec![2,3]),
+      (vec![0,1,2,3], vec![2], True, vec![0,1,2,3], vec![2]),
+      (vec![3], vec![0,1,2,3], False, vec![0,1,2,3], vec![3])
+    ]
+  }
+
+  fn test_powerset<S, F>(test_id: usize, make: F) where
+   S: Lattice + Bot + Intersection<Output=S> + Clone + Debug + Display + Eq,
+   ZeroToThree: Universe<S>,
+   F: Fn(Vec<u32>) -> S
+  {
+    let mut data_a = vec![];
+    let mut data_b = vec![];
+    let mut expected_entailment = vec![];
+    let mut expected_join = vec![];
+    let mut expected_meet = vec![];
+    for (a, b, entail, join, meet) in test_table() {
+      data_a.push(Powerset::<S, ZeroToThree>::wrap(make(a)));
+      data_b.push(Powerset::wrap(make(b)));
+      expected_entailment.push(entail);
+      expected_join.push(Powerset::wrap(make(join)));
+      expected_meet.push(Powerset::wrap(make(meet)));
+    }
+    let tester = LatticeTester::new(test_id,
+      data_a, data_b, expected_entailment, expected_join, expected_meet);
+    tester.distributive().test_all();
+  }
+
+  #[test]
+  fn wrap_test() {
+    let a = Powerset::<BTreeSet<u32>, ZeroToThree>::wrap(BTreeSet::wrap(vec![1, 3, 4, 9].into_iter().collect()));
+    assert_eq!(a.unwrap(), BTreeSet::wrap(vec![1, 3].into_iter().collect()));
+    let b = Powerset::<BitSet, ZeroToThree>::wrap(BitSet::wrap(vec![0, 1, 2, 3, 7].into_iter().collect()));
+    assert_eq!(b, Powerset::top());
+    assert_eq!(Powerset::<BitSet, ZeroToThree>::top().entail(&b), True);
+  }
+
+  #[test]
+  fn btree_set_lattice_test() {
+    test_powerset(0, |v| BTreeSet::wrap(v.into_iter().collect()));
+  }
+
+  #[test]
+  fn hash_set_lattice_test() {
+    test_powerset(1, |v| HashSet::wrap(v.into_iter().collect()));
+  }
+
+  #[test]
+  fn bit_set_lattice_test() {
+    test_powerset(2, |v| BitSet::wrap(v.into_iter().map(|x| x as usize).collect()));
+  }
+
+  /// Every subset of the universe `{0, 1, 2, 3}`.
+  fn all_subsets() -> Vec<Vec<u32>> {
+    (0..16u32).map(|bits| (0..4).filter(|x| bits & (1 << x) != 0).collect()).collect()
+  }
+
+  fn test_complement<S, F>(make: F) where
+   S: Union<Output=S> + Intersection<Output=S> + Difference<Output=S> + Subset + Disjoint + Bot + Debug + Eq,
+   ZeroToThree: Universe<S>,
+   F: Fn(Vec<u32>) -> S
+  {
+    let p = |v: Vec<u32>| Powerset::<S, ZeroToThree>::wrap(make(v));
+    assert_eq!(Powerset::<S, ZeroToThree>::bot().complement(), Powerset::top());
+    assert_eq!(Powerset::<S, ZeroToThree>::top().complement(), Powerset::bot());
+    assert_eq!(p(vec![0, 2]).complement(), p(vec![1, 3]));
+    // The elements outside of the universe are dropped by `wrap` and never come back through the complement.
+    assert_eq!(p(vec![1, 7]).complement(), p(vec![0, 2, 3]));
+    assert_eq!(p(vec![1, 7]).complement().complement(), p(vec![1]));
+    assert_eq!(p(vec![0, 1, 2, 3, 7]).complement(), Powerset::bot());
+    for a in all_subsets() {
+      let a = p(a);
+      assert_eq!(a.complement().complement(), a, "double complement of {:?}", a);
+      assert!(a.is_disjoint(&a.complement()), "{:?} overlaps its complement", a);
+      assert_eq!(a.union(&a.complement()), Powerset::top());
+      for b in all_subsets() {
+        let b = p(b);
+        assert_eq!(a.union(&b).complement(), a.complement().intersection(&b.complement()),
+          "De Morgan's law on the union of {:?} and {:?}", a, b);
+        assert_eq!(a.intersection(&b).complement(), a.complement().union(&b.complement()),
+          "De Morgan's law on the intersection of {:?} and {:?}", a, b);
+        assert_eq!(a.is_subset(&b), b.complement().is_subset(&a.complement()),
+          "the complement does not reverse the inclusion of {:?} in {:?}", a, b);
+      }
+    }
+  }
+
+  #[test]
+  fn complement_test() {
+    test_complement(|v| BTreeSet::wrap(v.into_iter().collect()));
+    test_complement(|v| HashSet::wrap(v.into_iter().collect()));
+    test_complement(|v| BitSet::wrap(v.into_iter().map(|x| x as usize).collect()));
+  }
+}
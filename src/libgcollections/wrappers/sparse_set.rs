@@ -0,0 +1,368 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Sparse sets of integers in `[0..capacity[`, as used by constraint solvers to represent the domains of the variables.
+//!
+//! The set is stored as a permutation of `[0..capacity[` in a dense array, the values of the set being its `size` first elements, and a sparse array giving the position of every value in the dense array.
+//! The membership test and the removal of a value are in O(1): a removed value is swapped with the last value of the set and the size is decremented.
+//! The removed values stay in the dense array, thus restoring a previous size restores the values removed since then.
+//! This is the `Snapshot` implementation: the label is the size of the set, which makes backtracking O(1).
+//!
+//! ```rust
+//! use gcollections::ops::*;
+//! use gcollections::SparseSet;
+//!
+//! let mut domain = SparseSet::new(10);
+//! let label = domain.snapshot();
+//! domain.remove(&3);
+//! domain.remove(&7);
+//! assert_eq!(domain.size(), 8);
+//! assert!(!domain.contains(&3));
+//! domain.restore(label);
+//! assert_eq!(domain, SparseSet::new(10));
+//! ```
+
+use kind::*;
+use ops::*;
+use wrappers::powerset::display_set;
+use std::cmp::max;
+use std::slice;
+use std::fmt::{Formatter, Display, Error};
+
+#[derive(Clone, Debug)]
+pub struct SparseSet
+{
+  dense: Vec<usize>,
+  sparse: Vec<usize>,
+  size: usize
+}
+
+impl SparseSet
+{
+  /// The set `{0, .., capacity - 1}`.
+  pub fn new(capacity: usize) -> SparseSet {
+    SparseSet {
+      dense: (0..capacity).collect(),
+      sparse: (0..capacity).collect(),
+      size: capacity
+    }
+  }
+
+  /// The set of `values`, it panics if a value is not smaller than `capacity`.
+  pub fn from_values(capacity: usize, values: &[usize]) -> SparseSet {
+    let mut set = SparseSet::new(capacity);
+    set.size = 0;
+    for &value in values {
+      assert!(value < capacity, "The value {} is outside of the capacity {} of the sparse set.", value, capacity);
+      if !set.contains(&value) {
+        let first_absent = set.dense[set.size];
+        set.swap(value, first_absent);
+        set.size += 1;
+      }
+    }
+    set
+  }
+
+  /// The values of the set are in `[0..capacity()[`.
+  pub fn capacity(&self) -> usize {
+    self.dense.len()
+  }
+
+  /// Iterates over the values in an unspecified order, which changes with the removals.
+  pub fn iter<'a>(&'a self) -> slice::Iter<'a, usize> {
+    self.dense[..self.size].iter()
+  }
+
+  /// Swaps the positions of `a` and `b` in the dense array.
+  fn swap(&mut self, a: usize, b: usize) {
+    let (i, j) = (self.sparse[a], self.sparse[b]);
+    self.dense.swap(i, j);
+    self.sparse[a] = j;
+    self.sparse[b] = i;
+  }
+
+  /// The values of `self` satisfying `pred`, the capacity of the result is `capacity`.
+  fn filter<P>(&self, capacity: usize, pred: P) -> SparseSet where
+    P: Fn(&usize) -> bool
+  {
+    let values: Vec<usize> = self.iter().cloned().filter(|v| pred(v)).collect();
+    SparseSet::from_values(capacity, &values)
+  }
+}
+
+impl Collection for SparseSet {
+  type Item = usize;
+}
+
+/// Two sets are equal if they have the same capacity and the same values, regardless of the order of the dense arrays.
+/// The capacity is the universe of the complement, thus equal sets have equal complements.
+impl PartialEq for SparseSet
+{
+  fn eq(&self, other: &SparseSet) -> bool {
+    self.capacity() == other.capacity() && self.size == other.size && self.is_subset(other)
+  }
+}
+
+impl Eq for SparseSet {}
+
+impl Contains for SparseSet
+{
+  fn contains(&self, value: &usize) -> bool {
+    *value < self.capacity() && self.sparse[*value] < self.size
+  }
+}
+
+/// Swaps `value` with the last value of the set, thus it can be restored with `Snapshot::restore`.
+impl Remove for SparseSet
+{
+  fn remove(&mut self, value: &usize) -> bool {
+    if !self.contains(value) {
+      return false;
+    }
+    let last = self.dense[self.size - 1];
+    self.swap(*value, last);
+    self.size -= 1;
+    true
+  }
+}
+
+/// The label is the size of the set, the values removed after a snapshot are restored by resetting the size.
+impl Snapshot for SparseSet
+{
+  type Label = usize;
+
+  fn snapshot(&self) -> usize {
+    self.size
+  }
+
+  fn restore(&mut self, label: usize) {
+    debug_assert!(label >= self.size && label <= self.capacity(), "The label does not come from a snapshot of this sparse set.");
+    self.size = label;
+  }
+}
+
+impl Cardinality for SparseSet
+{
+  type Size = usize;
+
+  fn size(&self) -> usize {
+    self.size
+  }
+}
+
+/// The set of capacity `0`.
+impl Empty for SparseSet
+{
+  fn empty() -> SparseSet {
+    SparseSet::new(0)
+  }
+}
+
+/// The bounds are computed in O(size) since the dense array is unordered.
+impl Bounded for SparseSet
+{
+  fn lower(&self) -> usize {
+    debug_assert!(self.size > 0, "Cannot access the lower bound of an empty sparse set.");
+    *self.iter().min().unwrap()
+  }
+
+  fn upper(&self) -> usize {
+    debug_assert!(self.size > 0, "Cannot access the upper bound of an empty sparse set.");
+    *self.iter().max().unwrap()
+  }
+}
+
+// The result of a binary operation has the largest capacity of its operands.
+
+impl Intersection for SparseSet
+{
+  type Output = SparseSet;
+
+  fn intersection(&self, other: &SparseSet) -> SparseSet {
+    self.filter(max(self.capacity(), other.capacity()), |v| other.contains(v))
+  }
+}
+
+impl Union for SparseSet
+{
+  type Output = SparseSet;
+
+  fn union(&self, other: &SparseSet) -> SparseSet {
+    let values: Vec<usize> = self.iter().chain(other.iter()).cloned().collect();
+    SparseSet::from_values(max(self.capacity(), other.capacity()), &values)
+  }
+}
+
+impl Difference for SparseSet
+{
+  type Output = SparseSet;
+
+  fn difference(&self, other: &SparseSet) -> SparseSet {
+    self.filter(max(self.capacity(), other.capacity()), |v| !other.contains(v))
+  }
+}
+
+impl SymmetricDifference for SparseSet
+{
+  type Output = SparseSet;
+
+  fn symmetric_difference(&self, other: &SparseSet) -> SparseSet {
+    self.difference(other).union(&other.difference(self))
+  }
+}
+
+/// The complement in `[0..capacity()[`, which are the removed values of the dense array.
+impl Complement for SparseSet
+{
+  fn complement(&self) -> SparseSet {
+    SparseSet::from_values(self.capacity(), &self.dense[self.size..])
+  }
+}
+
+impl Disjoint for SparseSet
+{
+  fn is_disjoint(&self, other: &SparseSet) -> bool {
+    let (small, large) = if self.size <= other.size { (self, other) } else { (other, self) };
+    small.iter().all(|v| !large.contains(v))
+  }
+}
+
+impl Overlap for SparseSet
+{
+  fn overlap(&self, other: &SparseSet) -> bool {
+    !self.is_disjoint(other)
+  }
+}
+
+impl Subset for SparseSet
+{
+  fn is_subset(&self, other: &SparseSet) -> bool {
+    self.size <= other.size && self.iter().all(|v| other.contains(v))
+  }
+}
+
+impl ProperSubset for SparseSet
+{
+  fn is_proper_subset(&self, other: &SparseSet) -> bool {
+    self.size < other.size && self.is_subset(other)
+  }
+}
+
+/// The values are displayed in increasing order.
+impl Display for SparseSet
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    let mut values: Vec<usize> = self.iter().cloned().collect();
+    values.sort();
+    display_set(values.iter(), formatter)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ops::set::test::*;
+  use wrappers::btree_set::BTreeSet;
+
+  fn set(values: &[usize]) -> SparseSet {
+    SparseSet::from_values(8, values)
+  }
+
+  fn test_data() -> Vec<SparseSet> {
+    vec![set(&[]), set(&[1]), set(&[1,2]), set(&[2,3]), set(&[3,2,1]), set(&[0,7]), set(&[0,1,2,3,4,5,6,7]),
+      SparseSet::from_values(20, &[1,15])]
+  }
+
+  fn to_set(a: &SparseSet) -> BTreeSet<usize> {
+    BTreeSet::wrap(a.iter().cloned().collect())
+  }
+
+  #[test]
+  fn constructors_test() {
+    assert!(SparseSet::empty().is_empty());
+    assert_eq!(SparseSet::new(5).size(), 5);
+    assert_eq!(SparseSet::new(5), SparseSet::from_values(5, &[4,3,2,1,0]));
+    assert_eq!(set(&[1,1,3]).size(), 2);
+    assert!(set(&[6]).is_singleton());
+    assert_eq!(set(&[5,2,6]).lower(), 2);
+    assert_eq!(set(&[5,2,6]).upper(), 6);
+    assert_eq!(set(&[1,3]), SparseSet::from_values(8, &[3,1]));
+    assert_ne!(set(&[1,3]), SparseSet::from_values(20, &[3,1]));
+    assert_eq!(format!("{}", set(&[5,2,6])), "{2, 5, 6}");
+    assert_eq!(format!("{}", SparseSet::empty()), "{}");
+  }
+
+  #[test]
+  #[should_panic(expected = "outside of the capacity")]
+  fn from_values_out_of_range_test() {
+    SparseSet::from_values(4, &[1, 4]);
+  }
+
+  #[test]
+  fn remove_test() {
+    let mut s = SparseSet::new(5);
+    assert!(s.remove(&2));
+    assert!(!s.remove(&2));
+    assert!(!s.remove(&9));
+    assert!(s.remove(&4));
+    assert_eq!(s, SparseSet::from_values(5, &[0,1,3]));
+    assert_eq!(s.complement(), SparseSet::from_values(5, &[2,4]));
+    assert!(s.remove(&0));
+    assert!(s.remove(&1));
+    assert!(s.remove(&3));
+    assert!(s.is_empty());
+    assert_eq!(s.complement(), SparseSet::new(5));
+  }
+
+  #[test]
+  fn snapshot_test() {
+    let mut s = SparseSet::new(8);
+    let root = s.snapshot();
+    s.remove(&0);
+    s.remove(&5);
+    s.remove(&6);
+    s.remove(&7);
+    let node = s.snapshot();
+    s.remove(&3);
+    s.remove(&1);
+    assert_eq!(s, set(&[2,4]));
+    s.restore(node);
+    assert_eq!(s, set(&[1,2,3,4]));
+    s.remove(&2);
+    assert_eq!(s, set(&[1,3,4]));
+    s.restore(node);
+    assert_eq!(s, set(&[1,2,3,4]));
+    s.restore(root);
+    assert_eq!(s, SparseSet::new(8));
+  }
+
+  #[test]
+  fn set_test() {
+    for a in test_data() {
+      for x in 0..22 {
+        assert_eq!(a.contains(&x), to_set(&a).contains(&x), "{} contains {}", a, x);
+        assert_eq!(a.contains(&x) || x >= a.capacity(), !a.complement().contains(&x), "{} in the complement of {}", x, a);
+      }
+      assert_eq!(a.complement().complement(), a);
+      for b in test_data() {
+        let (sa, sb) = (to_set(&a), to_set(&b));
+        assert!(a != b || a.complement() == b.complement(), "{} and {} are equal but not their complements", a, b);
+        assert_eq!(a.is_disjoint(&b), sa.is_disjoint(&sb), "{} disjoint {}", a, b);
+        assert_eq!(a.overlap(&b), sa.overlap(&sb), "{} overlap {}", a, b);
+        assert_eq!(a.is_subset(&b), sa.is_subset(&sb), "{} subset {}", a, b);
+        assert_eq!(a.is_proper_subset(&b), sa.is_proper_subset(&sb), "{} proper subset {}", a, b);
+        assert_eq!(to_set(&a.intersection(&b)), sa.intersection(&sb), "{} intersection {}", a, b);
+        assert_eq!(to_set(&a.union(&b)), sa.union(&sb), "{} union {}", a, b);
+        assert_eq!(to_set(&a.difference(&b)), sa.difference(&sb), "{} difference {}", a, b);
+        assert_eq!(to_set(&a.symmetric_difference(&b)), sa.symmetric_difference(&sb),
+          "{} symmetric difference {}", a, b);
+      }
+    }
+    MaySetTester::new(0, test_data(), (0..22).collect()).test_all();
+  }
+}
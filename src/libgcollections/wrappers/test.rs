@@ -0,0 +1,149 @@
+// Copyright 2018 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests shared by the wrappers having the same semantics over different underlying collections.
+//! A test is given a function building the tested collection from a list of values.
+
+use kind::*;
+use ops::*;
+use wrappers::optional::*;
+use ops::lattice::combinators::Flat::{Bottom, Value, Topmost};
+use trilean::SKleene;
+use std::fmt::Debug;
+use std::iter::FromIterator;
+
+pub fn known(x: i32) -> FlatOptional<i32> {
+  Value(Optional::singleton(x))
+}
+
+/// Tests a pointwise map lattice built by `map` from a list of entries.
+pub fn test_map<M, F>(map: F) where
+ M: Join + Meet + Entailment + Bot + Empty + Cardinality<Size=usize> + Contains + Collection<Item=u32> + Clone + Debug + Eq,
+ F: Fn(Vec<(u32, FlatOptional<i32>)>) -> M
+{
+  test_key_set(&map);
+  test_bot_values(&map);
+  test_lattice(&map);
+}
+
+fn test_key_set<M, F>(map: &F) where
+ M: Empty + Cardinality<Size=usize> + Contains + Collection<Item=u32>,
+ F: Fn(Vec<(u32, FlatOptional<i32>)>) -> M
+{
+  let m = map(vec![(1, known(1)), (3, known(2))]);
+  assert_eq!(m.size(), 2);
+  assert!(m.contains(&1));
+  assert!(!m.contains(&2));
+  assert!(M::empty().is_empty());
+}
+
+/// A key mapped to `bot` is the same as a missing key.
+fn test_bot_values<M, F>(map: &F) where
+ M: Meet + Bot + Cardinality<Size=usize> + Contains + Collection<Item=u32> + Debug + Eq,
+ F: Fn(Vec<(u32, FlatOptional<i32>)>) -> M
+{
+  let a = map(vec![(1, known(1)), (2, Bottom)]);
+  assert_eq!(a, map(vec![(1, known(1))]));
+  assert!(!a.contains(&2));
+  assert_eq!(map(vec![(1, Bottom)]), M::bot());
+  assert_eq!(map(vec![(1, known(1))]).meet(map(vec![(1, known(2))])), M::bot());
+}
+
+fn test_lattice<M, F>(map: &F) where
+ M: Join + Meet + Entailment + Bot + Clone + Debug + Eq,
+ F: Fn(Vec<(u32, FlatOptional<i32>)>) -> M
+{
+  let cases = vec![
+    // a, b, a |= b, a join b, a meet b
+    (map(vec![]), map(vec![]), SKleene::True, map(vec![]), map(vec![])),
+    (map(vec![(1, known(1))]), map(vec![]), SKleene::True, map(vec![(1, known(1))]), map(vec![])),
+    (map(vec![(1, known(1))]), map(vec![(2, known(2))]), SKleene::Unknown,
+      map(vec![(1, known(1)), (2, known(2))]), map(vec![])),
+    (map(vec![(1, known(1))]), map(vec![(1, known(2)), (2, known(2))]), SKleene::Unknown,
+      map(vec![(1, Topmost), (2, known(2))]), map(vec![])),
+    (map(vec![(1, known(1))]), map(vec![(1, Topmost)]), SKleene::False,
+      map(vec![(1, Topmost)]), map(vec![(1, known(1))])),
+    (map(vec![(1, known(1)), (2, known(2))]), map(vec![(2, known(2))]), SKleene::True,
+      map(vec![(1, known(1)), (2, known(2))]), map(vec![(2, known(2))]))
+  ];
+  for (a, b, entail, join, meet) in cases {
+    assert_eq!(a.entail(&b), entail, "{:?} |= {:?}", a, b);
+    assert_eq!(b.entail(&a), if a == b { SKleene::True } else { !entail }, "{:?} |= {:?}", b, a);
+    assert_eq!(a.clone().join(b.clone()), join, "{:?} join {:?}", a, b);
+    assert_eq!(b.clone().join(a.clone()), join, "{:?} join {:?}", b, a);
+    assert_eq!(a.clone().meet(b.clone()), meet, "{:?} meet {:?}", a, b);
+    assert_eq!(b.clone().meet(a.clone()), meet, "{:?} meet {:?}", b, a);
+    assert_eq!(a.clone().join(M::bot()), a);
+    assert_eq!(a.clone().meet(M::bot()), M::bot());
+  }
+}
+
+/// Tests the cardinality, the constructors, `Insert`, `Extract` and the set predicates of a set built by `set`.
+/// The predicates are checked against a reference computation on the lists of values.
+pub fn test_set<S, F>(set: F) where
+ S: Empty + Singleton + Cardinality<Size=usize> + IsEmpty + IsSingleton + Insert + Extract
+  + Disjoint + Overlap + Subset + ProperSubset + Union<Output=S> + Intersection<Output=S>
+  + Collection + Debug + Eq,
+ S::Item: From<u8> + Ord + Debug,
+ F: Fn(&[u8]) -> S
+{
+  test_set_constructors(&set);
+  test_set_insert_extract(&set);
+  test_set_predicates(&set);
+}
+
+fn test_set_constructors<S, F>(set: &F) where
+ S: Empty + Singleton + Cardinality<Size=usize> + IsEmpty + IsSingleton + Collection + Debug + Eq,
+ S::Item: From<u8>,
+ F: Fn(&[u8]) -> S
+{
+  let empty = S::empty();
+  assert!(empty.is_empty());
+  assert_eq!(empty.size(), 0);
+  let one = S::singleton(S::Item::from(1));
+  assert!(one.is_singleton());
+  assert_eq!(one, set(&[1]));
+  assert_eq!(set(&[1,2,3]).size(), 3);
+}
+
+fn test_set_insert_extract<S, F>(set: &F) where
+ S: Empty + Insert + Extract + Collection + Debug + Eq,
+ S::Item: From<u8> + Ord + Debug,
+ F: Fn(&[u8]) -> S
+{
+  let mut s = S::empty();
+  Insert::insert(&mut s, S::Item::from(3));
+  Insert::insert(&mut s, S::Item::from(1));
+  Insert::insert(&mut s, S::Item::from(3));
+  assert_eq!(s, set(&[1,3]));
+  let mut extracted = vec![s.extract().unwrap(), s.extract().unwrap()];
+  extracted.sort();
+  assert_eq!(extracted, vec![S::Item::from(1), S::Item::from(3)]);
+  assert_eq!(s.extract(), None);
+}
+
+fn test_set_predicates<S, F>(set: &F) where
+ S: Cardinality<Size=usize> + Disjoint + Overlap + Subset + ProperSubset + Union<Output=S> + Intersection<Output=S> + Debug + Eq,
+ F: Fn(&[u8]) -> S
+{
+  let test_data: Vec<&[u8]> = vec![&[], &[1], &[1,2], &[2,3], &[1,2,3], &[4], &[100]];
+  for va in &test_data {
+    for vb in &test_data {
+      let (a, b) = (set(va), set(vb));
+      let subset = va.iter().all(|x| vb.contains(x));
+      let common = va.iter().filter(|x| vb.contains(x)).count();
+      let union: Vec<u8> = Vec::from_iter(va.iter().chain(vb.iter().filter(|x| !va.contains(x))).cloned());
+      assert_eq!(a.is_disjoint(&b), common == 0, "{:?} disjoint {:?}", a, b);
+      assert_eq!(a.overlap(&b), common > 0, "{:?} overlap {:?}", a, b);
+      assert_eq!(a.is_subset(&b), subset, "{:?} subset {:?}", a, b);
+      assert_eq!(a.is_proper_subset(&b), subset && va.len() < vb.len(), "{:?} proper_subset {:?}", a, b);
+      assert_eq!(a.union(&b).size(), union.len());
+      assert_eq!(a.intersection(&b).size(), common);
+    }
+  }
+}